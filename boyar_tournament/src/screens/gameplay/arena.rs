@@ -3,7 +3,10 @@ use bevy::window::PrimaryWindow;
 use bevy_aseprite_ultra::prelude::*;
 use bevy_asset_loader::prelude::*;
 
-use crate::{scaling::DynamicScale, screens::GameState};
+use crate::{
+    scaling::DynamicScale,
+    screens::{GameState, InMatch},
+};
 
 use crate::scaling::DrawRegion;
 use common::ArenaPos;
@@ -19,18 +22,15 @@ pub(super) fn plugin(app: &mut App) {
         LoadingStateConfig::new(GameState::Loading).load_collection::<ArenaAssets>(),
     );
 
-    app.add_systems(OnEnter(GameState::Gameplay), spawn_arena);
+    app.add_systems(OnEnter(InMatch), spawn_arena);
 
     app.add_systems(
         Update,
-        (update_arena_pos, update_mouse_arena_pos).run_if(in_state(GameState::Gameplay)),
+        (update_arena_pos, update_mouse_arena_pos).run_if(in_state(InMatch)),
     );
 
     #[cfg(debug_assertions)]
-    app.add_systems(
-        Update,
-        draw_arena_region_outline.run_if(in_state(GameState::Gameplay)),
-    );
+    app.add_systems(Update, draw_arena_region_outline.run_if(in_state(InMatch)));
 }
 
 #[derive(AssetCollection, Resource)]
@@ -48,14 +48,14 @@ fn spawn_arena(mut cmd: Commands, arena_assets: ResMut<ArenaAssets>) {
             name: "winter_arena".into(),
             aseprite: arena_assets.arena.clone(),
         },
-        StateScoped(GameState::Gameplay),
+        StateScoped(InMatch),
         DynamicScale(1.),
         Transform::from_translation(Vec3::ZERO.with_z(-0.5)),
     ));
     cmd.spawn((
         AudioPlayer::new(arena_assets.battle_music.clone()),
         PlaybackSettings::LOOP,
-        StateScoped(GameState::Gameplay),
+        StateScoped(InMatch),
     ));
 }
 
@@ -73,7 +73,7 @@ fn update_arena_pos(
             arena_pos.1 * draw_region.height / 43.2 + draw_region.height / 13.5;
 
         // Чем ниже сущность на арене тем "выше" она отображается
-        transform.translation.z = transform.translation.y / draw_region.height * -1.;
+        transform.translation.z = -transform.translation.y / draw_region.height;
 
         if let Some(height_offset) = height_offset {
             transform.translation.y += height_offset.0 * draw_region.height / 43.2;
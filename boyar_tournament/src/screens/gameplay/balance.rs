@@ -0,0 +1,84 @@
+use std::marker::PhantomData;
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext},
+    prelude::*,
+};
+use bevy_asset_loader::prelude::*;
+use common::{parse_ron, CardsData, UnitsData};
+use serde::Deserialize;
+
+use crate::screens::GameState;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<UnitsAsset>();
+    app.init_asset::<CardsAsset>();
+    app.register_asset_loader(RonLoader::<UnitsAsset>::new(&["units.ron"]));
+    app.register_asset_loader(RonLoader::<CardsAsset>::new(&["cards.ron"]));
+
+    app.configure_loading_state(
+        LoadingStateConfig::new(GameState::Loading).load_collection::<BalanceAssets>(),
+    );
+    app.add_systems(OnExit(GameState::Loading), insert_balance);
+}
+
+#[derive(Asset, TypePath, Deserialize)]
+#[serde(transparent)]
+struct UnitsAsset(UnitsData);
+
+#[derive(Asset, TypePath, Deserialize)]
+#[serde(transparent)]
+struct CardsAsset(CardsData);
+
+#[derive(AssetCollection, Resource)]
+struct BalanceAssets {
+    // Пути совпадают с common::UNITS_PATH и common::CARDS_PATH
+    #[asset(path = "data/units.ron")]
+    units: Handle<UnitsAsset>,
+    #[asset(path = "data/cards.ron")]
+    cards: Handle<CardsAsset>,
+}
+
+struct RonLoader<A> {
+    extensions: &'static [&'static str],
+    _asset: PhantomData<fn() -> A>,
+}
+impl<A> RonLoader<A> {
+    fn new(extensions: &'static [&'static str]) -> Self {
+        Self {
+            extensions,
+            _asset: PhantomData,
+        }
+    }
+}
+impl<A: Asset + for<'de> Deserialize<'de>> AssetLoader for RonLoader<A> {
+    type Asset = A;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<A, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(parse_ron(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        self.extensions
+    }
+}
+
+/// Характеристики нужны в виде ресурсов, как и на сервере
+fn insert_balance(
+    assets: Res<BalanceAssets>,
+    units: Res<Assets<UnitsAsset>>,
+    cards: Res<Assets<CardsAsset>>,
+    mut cmd: Commands,
+) {
+    cmd.insert_resource(units.get(&assets.units).unwrap().0.clone());
+    cmd.insert_resource(cards.get(&assets.cards).unwrap().0.clone());
+}
@@ -1,8 +1,12 @@
-use bevy::{input::common_conditions::input_just_released, prelude::*};
+use std::time::Duration;
+
+use bevy::{input::common_conditions::input_just_released, prelude::*, utils::HashMap};
 use bevy_aseprite_ultra::prelude::*;
 use bevy_asset_loader::prelude::*;
-use bevy_quinnet::client::QuinnetClient;
-use common::{ArenaPos, Card, ClientChannel, ClientMessage, PlayerNumber};
+use common::{
+    ArenaPos, Card, CardsData, ClientMessage, Direction, Level, PlayerNumber, UnitsData,
+    ELIXIR_INTERVAL, MAX_ELIXIR,
+};
 use rand::{seq::SliceRandom, thread_rng};
 
 use crate::{
@@ -13,21 +17,33 @@ use crate::{
     },
 };
 
-use super::{arena::MouseArenaPos, spawn_text, FontAssets};
+use super::{
+    arena::MouseArenaPos,
+    networking::ServerConnection,
+    spawn_text,
+    units::{insert_sprite, UnitSprites},
+    FontAssets,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<Deck>();
     app.register_type::<DeckIndex>();
     app.register_type::<SelectedCard>();
     app.register_type::<ElixirCounter>();
+    app.register_type::<CardLevels>();
 
     app.init_resource::<SelectedCard>();
     app.init_resource::<ElixirCounter>();
+    app.init_resource::<CardLevels>();
+    app.init_resource::<PlayedFrom>();
 
     app.configure_loading_state(
         LoadingStateConfig::new(GameState::Loading).load_collection::<CardsAssets>(),
     );
 
+    // Только карты, для которых есть картинка в cards.aseprite.
+    // Стрелы, Пушка, Изба, Заморозка и Ярость вернутся в колоду
+    // вместе со своими картинками
     use Card::*;
     let mut cards = [
         Rus,
@@ -38,9 +54,13 @@ pub(super) fn plugin(app: &mut App) {
         BatHorde,
         Bomber,
         Giant,
+        Fireball,
+        Zap,
     ];
     cards.shuffle(&mut thread_rng());
-    app.insert_resource(Deck(cards));
+    let mut deck = [Rus; 8];
+    deck.copy_from_slice(&cards[..8]);
+    app.insert_resource(Deck(deck));
 
     app.add_systems(
         Update,
@@ -53,7 +73,8 @@ pub(super) fn plugin(app: &mut App) {
     );
     app.add_systems(
         Update,
-        update_elixir_counter.run_if(in_state(GameState::Gameplay)),
+        (update_elixir_counter, update_placement_preview)
+            .run_if(in_state(GameState::Gameplay)),
     );
 
     app.add_systems(
@@ -61,6 +82,9 @@ pub(super) fn plugin(app: &mut App) {
         (spawn_card_hand, spawn_elixir_counter),
     );
     app.add_observer(update_card_hand);
+    app.add_observer(send_deck);
+    app.add_observer(add_elixir);
+    app.add_observer(reject_play);
 }
 
 #[derive(AssetCollection, Resource)]
@@ -79,10 +103,47 @@ struct Deck([Card; 8]);
 #[reflect(Component)]
 struct DeckIndex(u8);
 
+/// Уровни карт игрока. Прокачки пока нет, поэтому уровни не сохраняются
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+struct CardLevels(HashMap<Card, Level>);
+impl CardLevels {
+    fn get(&self, card: Card) -> Level {
+        self.0.get(&card).copied().unwrap_or_default()
+    }
+}
+
+/// Текст с уровнем карты в руке
+#[derive(Component)]
+struct CardLevelText(u8);
+
+fn level_text(level: Level) -> String {
+    format!("Ур. {}", level.0)
+}
+
+/// Сервер начал матч, отправляем ему колоду с уровнями карт
+#[derive(Event)]
+pub(super) struct SendDeck;
+
+fn send_deck(
+    _: Trigger<SendDeck>,
+    deck: Res<Deck>,
+    levels: Res<CardLevels>,
+    mut connection: ServerConnection,
+) {
+    let deck = deck
+        .0
+        .iter()
+        .map(|&card| (card, levels.get(card)))
+        .collect();
+    connection.send(ClientMessage::SetDeck(deck));
+}
+
 fn spawn_card_hand(
     mut cmd: Commands,
     cards_assets: ResMut<CardsAssets>,
     deck: Res<Deck>,
+    levels: Res<CardLevels>,
     font: Res<FontAssets>,
 ) {
     for (i, (pos, card)) in [-2.05, -0.22, 1.62, 3.45].iter().zip(deck.0).enumerate() {
@@ -99,6 +160,20 @@ fn spawn_card_hand(
             UiHitbox(1.8, 2.3),
         ))
         .observe(on_card_select);
+
+        let level = spawn_text(
+            &mut cmd,
+            &level_text(levels.get(card)),
+            font.font.clone(),
+            25.,
+            Color::WHITE,
+            1.,
+            (*pos, -7.25),
+            GameState::Gameplay,
+        );
+        for entity in level {
+            cmd.entity(entity).insert(CardLevelText(i as _));
+        }
     }
 
     spawn_text(
@@ -127,14 +202,69 @@ fn spawn_card_hand(
 #[derive(Resource, Reflect)]
 #[reflect(Resource)]
 struct ElixirCounter(u8, Timer);
+
+#[derive(Component)]
+struct ElixirText;
 impl Default for ElixirCounter {
     fn default() -> Self {
-        Self(0, Timer::from_seconds(1.5, TimerMode::Repeating))
+        Self(
+            0,
+            Timer::from_seconds(ELIXIR_INTERVAL, TimerMode::Repeating),
+        )
     }
 }
 
+/// Эликсир за уничтоженного юнита противника
+#[derive(Event)]
+pub(super) struct AddElixir(pub u8);
+
+fn add_elixir(trigger: Trigger<AddElixir>, mut counter: ResMut<ElixirCounter>) {
+    counter.0 = (counter.0 + trigger.event().0).min(MAX_ELIXIR);
+}
+
+/// Сервер не принял розыгрыш: его эликсир расходится с нашим
+/// или карта легла не туда
+#[derive(Event)]
+pub(super) struct RejectPlay {
+    pub(super) card: Card,
+    pub(super) elixir: f32,
+}
+
+/// Слот руки, из которого карта разыграна последний раз
+#[derive(Resource, Default)]
+struct PlayedFrom(HashMap<Card, usize>);
+
+fn reject_play(
+    trigger: Trigger<RejectPlay>,
+    mut counter: ResMut<ElixirCounter>,
+    mut deck: ResMut<Deck>,
+    played: Res<PlayedFrom>,
+    mut cmd: Commands,
+) {
+    let &RejectPlay { card, elixir } = trigger.event();
+    counter.0 = elixir as u8;
+    counter
+        .1
+        .set_elapsed(Duration::from_secs_f32(elixir.fract() * ELIXIR_INTERVAL));
+
+    // Карта могла уже вернуться в руку, тогда двигать нечего
+    let Some(&index) = played.0.get(&card) else {
+        return;
+    };
+    let Some(queued) = deck.0[4..].iter().position(|&c| c == card) else {
+        return;
+    };
+    // Карта встаёт на своё место, занявшая его возвращается в начало очереди
+    let replacement = deck.0[index];
+    deck.0.copy_within(4..4 + queued, 5);
+    deck.0[4] = replacement;
+    deck.0[index] = card;
+
+    cmd.trigger(UpdateCardHand);
+}
+
 fn spawn_elixir_counter(mut cmd: Commands, font: Res<FontAssets>) {
-    spawn_text(
+    let text = spawn_text(
         &mut cmd,
         "0",
         font.font.clone(),
@@ -144,23 +274,22 @@ fn spawn_elixir_counter(mut cmd: Commands, font: Res<FontAssets>) {
         (0.7, -7.7),
         GameState::Gameplay,
     );
+    for entity in text {
+        cmd.entity(entity).insert(ElixirText);
+    }
 }
 
 fn update_elixir_counter(
     mut counter: ResMut<ElixirCounter>,
-    mut text: Query<&mut Text2d>,
+    mut text: Query<&mut Text2d, With<ElixirText>>,
     time: Res<Time>,
 ) {
-    if counter.1.tick(time.delta()).just_finished() {
-        if counter.0 < 10 {
-            counter.0 += 1;
-        }
+    if counter.1.tick(time.delta()).just_finished() && counter.0 < MAX_ELIXIR {
+        counter.0 += 1;
     }
 
     for mut text in &mut text {
-        if text.0 != "След." {
-            text.0 = counter.0.to_string();
-        }
+        text.0 = counter.0.to_string();
     }
 }
 
@@ -178,28 +307,17 @@ impl IntoTag for Card {
             Card::BatHorde => "bat_horde",
             Card::Bomber => "bomber",
             Card::Giant => "giant",
+            Card::Fireball => "fireball",
+            Card::Arrows => "arrows",
+            Card::Cannon => "cannon",
+            Card::Hut => "hut",
+            Card::Zap => "zap",
+            Card::Freeze => "freeze",
+            Card::Rage => "rage",
         };
         s.into()
     }
 }
-trait ElixirCost {
-    fn elixir_cost(&self) -> u8;
-}
-impl ElixirCost for Card {
-    fn elixir_cost(&self) -> u8 {
-        match self {
-            Card::Rus => 3,
-            Card::Musketeer => 4,
-            Card::ThreeMusketeers => 9,
-            Card::Priest => 5,
-            Card::Bats => 3,
-            Card::BatHorde => 5,
-            Card::Bomber => 3,
-            Card::Giant => 6,
-        }
-    }
-}
-
 #[derive(Resource, Reflect, Default)]
 #[reflect(Resource)]
 struct SelectedCard(Option<u8>);
@@ -243,10 +361,12 @@ fn play_card(
     mouse_pos: Res<MouseArenaPos>,
     selected_card: Res<SelectedCard>,
     mut deck: ResMut<Deck>,
-    mut client: ResMut<QuinnetClient>,
+    mut connection: ServerConnection,
     mut cmd: Commands,
     player_num: Res<PlayerNumber>,
     mut elixir: ResMut<ElixirCounter>,
+    cards: Res<CardsData>,
+    mut played: ResMut<PlayedFrom>,
 ) {
     let Some(mouse_pos) = mouse_pos.0 else {
         return;
@@ -257,31 +377,24 @@ fn play_card(
     let index = index as usize;
     let card = deck.0[index];
 
-    let cost = card.elixir_cost();
+    let cost = cards.stats(card).cost;
     if cost > elixir.0 {
         return;
     }
     elixir.0 -= cost;
 
-    // Ставим точку в центр клетки
-    let mut x = mouse_pos.0.floor() + 0.5;
-    let mut y = mouse_pos.1.floor().clamp(-16., -2.) + 0.5;
+    let ArenaPos(mut x, mut y) = placement(card, mouse_pos);
     if let PlayerNumber::Two = *player_num {
         x *= -1.;
         y *= -1.;
     }
 
-    client
-        .connection_mut()
-        .send_message_on(
-            ClientChannel::OrderedReliable,
-            ClientMessage::PlayCard {
-                card,
-                placement: ArenaPos(x, y),
-            },
-        )
-        .unwrap();
+    connection.send(ClientMessage::PlayCard {
+        card,
+        placement: ArenaPos(x, y),
+    });
 
+    played.0.insert(card, index);
     // Передвигаем карты в колоде на 1
     deck.0[index] = deck.0[4];
     deck.0[4] = deck.0[5];
@@ -292,13 +405,91 @@ fn play_card(
     cmd.trigger(UpdateCardHand);
 }
 
+/// Ставим точку в центр клетки.
+/// Юнитов можно ставить только на своей половине, заклинания - куда угодно
+fn placement(card: Card, mouse_pos: ArenaPos) -> ArenaPos {
+    let x = mouse_pos.0.floor() + 0.5;
+    let y = match card.spell() {
+        Some(_) => mouse_pos.1.floor() + 0.5,
+        None => mouse_pos.1.floor().clamp(-16., -2.) + 0.5,
+    };
+    ArenaPos(x, y)
+}
+
+/// Полупрозрачные юниты выбранной карты там, где они появятся
+#[derive(Component)]
+struct PlacementPreview {
+    card: Card,
+    offset: ArenaPos,
+}
+
+fn update_placement_preview(
+    mouse_pos: Res<MouseArenaPos>,
+    selected_card: Res<SelectedCard>,
+    deck: Res<Deck>,
+    cards: Res<CardsData>,
+    units: Res<UnitsData>,
+    mut sprites: ResMut<UnitSprites>,
+    asset_server: Res<AssetServer>,
+    mut previews: Query<(Entity, &PlacementPreview, &mut ArenaPos)>,
+    mut cmd: Commands,
+) {
+    let card = selected_card.0.map(|index| deck.0[index as usize]);
+    let placement = mouse_pos
+        .0
+        .zip(card)
+        .map(|(pos, card)| placement(card, pos));
+
+    let mut shown = false;
+    for (entity, preview, mut pos) in &mut previews {
+        match placement {
+            Some(placement) if card == Some(preview.card) => {
+                *pos = placement;
+                *pos += preview.offset;
+                shown = true;
+            }
+            _ => cmd.entity(entity).despawn(),
+        }
+    }
+    let (Some(card), Some(placement), false) = (card, placement, shown) else {
+        return;
+    };
+
+    // Клиент всегда видит себя первым игроком
+    for (unit, offset) in cards.stats(card).formation_for(PlayerNumber::One) {
+        let mut pos = placement;
+        pos += offset;
+        let mut preview = cmd.spawn((
+            Name::new("Предпросмотр"),
+            PlacementPreview { card, offset },
+            pos,
+            Sprite {
+                color: Color::srgba(1., 1., 1., 0.5),
+                ..default()
+            },
+            StateScoped(GameState::Gameplay),
+        ));
+        let view = &units.stats(unit).view;
+        insert_sprite(
+            &mut preview,
+            &view.sprite,
+            true,
+            Direction::Up,
+            &mut sprites,
+            &asset_server,
+        );
+    }
+}
+
 #[derive(Event)]
 struct UpdateCardHand;
 
 fn update_card_hand(
     _: Trigger<UpdateCardHand>,
     deck: Res<Deck>,
+    levels: Res<CardLevels>,
     mut query: Query<(&DeckIndex, &mut AseSpriteSlice, &mut DynamicScale)>,
+    mut level_texts: Query<(&CardLevelText, &mut Text2d)>,
     mut selected_card: ResMut<SelectedCard>,
 ) {
     for (index, mut sprite, mut scale) in &mut query {
@@ -309,6 +500,9 @@ fn update_card_hand(
         let card = deck.0[index.0 as usize];
         sprite.name = card.tag();
     }
+    for (index, mut text) in &mut level_texts {
+        text.0 = level_text(levels.get(deck.0[index.0 as usize]));
+    }
 
     selected_card.0 = None;
 }
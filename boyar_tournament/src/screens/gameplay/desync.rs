@@ -0,0 +1,110 @@
+use std::{env, fmt::Write, fs, path::PathBuf};
+
+use bevy::{prelude::*, utils::HashMap};
+use common::{Checksum, ChecksumEntry};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(check_sync);
+}
+
+/// Контрольная сумма сервера и юниты из того же SyncEntities
+#[derive(Event)]
+pub(super) struct CheckSync {
+    pub(super) checksum: Checksum,
+    pub(super) server: Vec<ChecksumEntry>,
+}
+
+/// Суммы локальной симуляции клиента по тикам, с ними сравниваются суммы сервера.
+/// Своей симуляции (предсказания) у клиента пока нет, поэтому ресурс никто
+/// не добавляет и рассинхронизация не проверяется. Сервер суммы уже присылает
+#[derive(Resource, Default)]
+pub(super) struct LocalChecksums {
+    pub(super) sums: HashMap<u64, (Checksum, Vec<ChecksumEntry>)>,
+    // После первого расхождения симуляции уже не сойдутся
+    desynced: bool,
+}
+
+/// Папку для состояний при рассинхронизации можно переопределить через BOYAR_DESYNC.
+/// По умолчанию desync в рабочей папке игры
+fn desync_dir() -> PathBuf {
+    env::var_os("BOYAR_DESYNC")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("desync"))
+}
+
+/// Сравнивает сумму сервера с суммой локальной симуляции на том же тике.
+/// Снимок сервера на клиенте тут не участвует: клиент берёт из него состояние
+/// целиком, и сумма по нему всегда совпала бы
+fn check_sync(trigger: Trigger<CheckSync>, local: Option<ResMut<LocalChecksums>>) {
+    let Some(mut local) = local else {
+        return;
+    };
+    let CheckSync { checksum, server } = trigger.event();
+    let sums = local.sums.remove(&checksum.tick);
+    // Снимки приходят по порядку, более старые суммы уже не понадобятся
+    local.sums.retain(|&tick, _| tick > checksum.tick);
+    let Some((local_checksum, mut entries)) = sums else {
+        return;
+    };
+    if local.desynced || local_checksum == *checksum {
+        return;
+    }
+    local.desynced = true;
+
+    let mut server = server.clone();
+    server.sort_unstable_by_key(|entry| entry.entity);
+    entries.sort_unstable_by_key(|entry| entry.entity);
+    warn!(
+        "Рассинхронизация на тике {}: сервер {:016x}, клиент {:016x}\n{}",
+        checksum.tick,
+        checksum.hash,
+        local_checksum.hash,
+        diff(&server, &entries)
+    );
+    dump(checksum.tick, &server, &entries);
+}
+
+/// Построчное сравнение юнитов по Entity сервера
+fn diff(server: &[ChecksumEntry], local: &[ChecksumEntry]) -> String {
+    let local_by_entity: HashMap<_, _> = local.iter().map(|e| (e.entity, e)).collect();
+    let mut out = String::new();
+    for entry in server {
+        match local_by_entity.get(&entry.entity) {
+            None => writeln!(out, "  {}: нет на клиенте", entry.entity).unwrap(),
+            Some(&local) if local != entry => writeln!(
+                out,
+                "  {}: сервер {entry:?}, клиент {local:?}",
+                entry.entity
+            )
+            .unwrap(),
+            Some(_) => {}
+        }
+    }
+    for entry in local {
+        if !server.iter().any(|e| e.entity == entry.entity) {
+            writeln!(out, "  {}: нет на сервере", entry.entity).unwrap();
+        }
+    }
+    out
+}
+
+/// Оба состояния по юниту на строку, чтобы их можно было сравнить diff-ом
+fn dump(tick: u64, server: &[ChecksumEntry], local: &[ChecksumEntry]) {
+    let dir = desync_dir();
+    let write = |side: &str, entries: &[ChecksumEntry]| {
+        let path = dir.join(format!("{tick}_{side}.txt"));
+        let text: String = entries.iter().map(|e| format!("{e:?}\n")).collect();
+        fs::write(&path, text).map(|_| path)
+    };
+    let result = fs::create_dir_all(&dir)
+        .and_then(|_| write("server", server))
+        .and_then(|server| Ok((server, write("client", local)?)));
+    match result {
+        Ok((server, client)) => info!(
+            "Состояния сохранены в {} и {}",
+            server.display(),
+            client.display()
+        ),
+        Err(e) => warn!("Не удалось сохранить состояния в {}: {e}", dir.display()),
+    }
+}
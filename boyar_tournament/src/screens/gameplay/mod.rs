@@ -7,20 +7,33 @@ use crate::scaling::{DynamicScale, DynamicTransform};
 use super::GameState;
 
 mod arena;
+mod balance;
 mod deck;
+mod desync;
 mod networking;
+mod practice;
 mod projectiles;
+mod replay;
+mod spells;
 mod units;
 
+pub(super) use networking::MatchMode;
+pub(super) use replay::ReplayFile;
+
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins(AsepriteUltraPlugin);
 
     app.add_plugins((
         arena::plugin,
+        balance::plugin,
         networking::plugin,
         units::plugin,
         deck::plugin,
+        desync::plugin,
         projectiles::plugin,
+        spells::plugin,
+        replay::plugin,
+        practice::plugin,
     ));
 
     app.configure_loading_state(
@@ -29,12 +42,12 @@ pub(super) fn plugin(app: &mut App) {
 }
 
 #[derive(AssetCollection, Resource)]
-struct FontAssets {
+pub(super) struct FontAssets {
     #[asset(path = "Keleti-Regular.ttf")]
-    font: Handle<Font>,
+    pub(super) font: Handle<Font>,
 }
 
-fn spawn_text(
+pub(super) fn spawn_text(
     cmd: &mut Commands,
     text: &str,
     font: Handle<Font>,
@@ -43,24 +56,30 @@ fn spawn_text(
     dynamic_scale: f32,
     dynamic_transform: (f32, f32),
     state: GameState,
-) {
-    cmd.spawn((
-        Text2d::new(text),
-        TextFont::from_font(font.clone()).with_font_size(font_size),
-        TextColor(color),
-        StateScoped(state),
-        DynamicScale(dynamic_scale),
-        DynamicTransform(dynamic_transform.0, dynamic_transform.1),
-    ))
-    .insert(Transform::from_xyz(0., 0., 0.2));
+) -> [Entity; 2] {
+    let text_entity = cmd
+        .spawn((
+            Text2d::new(text),
+            TextFont::from_font(font.clone()).with_font_size(font_size),
+            TextColor(color),
+            StateScoped(state),
+            DynamicScale(dynamic_scale),
+            DynamicTransform(dynamic_transform.0, dynamic_transform.1),
+        ))
+        .insert(Transform::from_xyz(0., 0., 0.2))
+        .id();
+
+    let shadow = cmd
+        .spawn((
+            Text2d::new(text),
+            TextFont::from_font(font.clone()).with_font_size(font_size),
+            TextColor(Color::BLACK),
+            StateScoped(state),
+            DynamicScale(dynamic_scale),
+            DynamicTransform(dynamic_transform.0 + 0.03, dynamic_transform.1 - 0.03),
+        ))
+        .insert(Transform::from_xyz(0., 0., 0.1))
+        .id();
 
-    cmd.spawn((
-        Text2d::new(text),
-        TextFont::from_font(font.clone()).with_font_size(font_size),
-        TextColor(Color::BLACK),
-        StateScoped(state),
-        DynamicScale(dynamic_scale),
-        DynamicTransform(dynamic_transform.0 + 0.03, dynamic_transform.1 - 0.03),
-    ))
-    .insert(Transform::from_xyz(0., 0., 0.1));
+    [text_entity, shadow]
 }
@@ -1,18 +1,23 @@
-use bevy::{prelude::*, utils::HashMap};
+use std::collections::VecDeque;
+
+use bevy::{ecs::system::SystemParam, prelude::*, utils::HashMap};
 use bevy_quinnet::client::{
     certificate::CertificateVerificationMode, connection::ClientEndpointConfiguration,
     QuinnetClient, QuinnetClientPlugin,
 };
 use common::{
-    ArenaPos, ClientChannel, Direction, Health, PlayerNumber, ServerMessage, UnitState,
-    LOCAL_BIND_IP, SERVER_HOST, SERVER_PORT,
+    ActiveEffects, ArenaPos, ChecksumEntry, ClientChannel, ClientMessage, Direction, Health,
+    PlayerNumber, ServerMessage, Shield, UnitState, LOCAL_BIND_IP, SERVER_HOST, SERVER_PORT,
 };
 
 use crate::screens::GameState;
 
 use super::{
+    deck::{AddElixir, RejectPlay, SendDeck},
+    desync::CheckSync,
     projectiles::SpawnProjectile,
-    units::{AssociatedTower, SpawnUnit},
+    spells::SpawnSpell,
+    units::{ActivateKingTower, AssociatedTower, SpawnUnit, UnitDeath},
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -22,13 +27,65 @@ pub(super) fn plugin(app: &mut App) {
     app.init_resource::<NetworkMapping>();
     app.register_type::<NetworkMapping>();
 
-    app.add_systems(OnEnter(GameState::Gameplay), start_connection);
+    app.init_resource::<MatchMode>();
+    app.add_systems(
+        OnEnter(GameState::Gameplay),
+        start_connection.run_if(resource_equals(MatchMode::Online)),
+    );
     app.add_systems(
         Update,
         handle_server_messages.run_if(in_state(GameState::Gameplay)),
     );
 }
 
+/// С кем идёт матч на экране Gameplay
+#[derive(Resource, Component, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum MatchMode {
+    // Сервер по SERVER_HOST
+    #[default]
+    Online,
+    // Бот в локальной симуляции, без сети
+    Practice,
+}
+
+/// Очереди сообщений между клиентом и сервером в том же процессе.
+/// Пока ресурс существует, сообщения идут через него, а не через QUIC
+#[derive(Resource, Default)]
+pub(super) struct LocalChannel {
+    pub(super) to_server: VecDeque<ClientMessage>,
+    pub(super) to_client: VecDeque<ServerMessage>,
+}
+
+/// Соединение с сервером, удалённым или локальным
+#[derive(SystemParam)]
+pub(super) struct ServerConnection<'w> {
+    client: ResMut<'w, QuinnetClient>,
+    local: Option<ResMut<'w, LocalChannel>>,
+}
+impl ServerConnection<'_> {
+    pub(super) fn send(&mut self, message: ClientMessage) {
+        match &mut self.local {
+            Some(local) => local.to_server.push_back(message),
+            None => self
+                .client
+                .connection_mut()
+                .send_message_on(ClientChannel::OrderedReliable, message)
+                .unwrap(),
+        }
+    }
+
+    fn receive(&mut self) -> Option<ServerMessage> {
+        match &mut self.local {
+            Some(local) => local.to_client.pop_front(),
+            None => self
+                .client
+                .connection_mut()
+                .try_receive_message::<ServerMessage>()
+                .map(|(_, message)| message),
+        }
+    }
+}
+
 fn start_connection(mut client: ResMut<QuinnetClient>) {
     client
         .open_connection(
@@ -39,7 +96,7 @@ fn start_connection(mut client: ResMut<QuinnetClient>) {
         .unwrap();
 }
 
-trait AdjustForPlayer {
+pub(super) trait AdjustForPlayer {
     fn adjust_for_player(&self, player_num: PlayerNumber) -> Self;
 }
 impl AdjustForPlayer for ArenaPos {
@@ -59,32 +116,59 @@ impl AdjustForPlayer for Direction {
     }
 }
 
-fn handle_server_messages(
-    mut client: ResMut<QuinnetClient>,
-    mut player_num: ResMut<PlayerNumber>,
-    mut cmd: Commands,
-    mut network_mapping: ResMut<NetworkMapping>,
-    mut units_query: Query<(&mut ArenaPos, &mut Direction, &mut UnitState, &mut Health)>,
-    mut projectiles_query: Query<&mut ArenaPos, Without<UnitState>>,
-    towers: Query<&AssociatedTower>,
+pub(super) fn handle_server_messages(
+    mut connection: ServerConnection,
+    mut handler: MessageHandler,
 ) {
-    while let Some((_, message)) = client
-        .connection_mut()
-        .try_receive_message::<ServerMessage>()
-    {
+    while let Some(message) = connection.receive() {
+        handler.handle(message);
+    }
+}
+
+/// Применяет ServerMessage к миру клиента.
+/// Сообщения приходят от сервера или от локальной симуляции
+#[derive(SystemParam)]
+pub(super) struct MessageHandler<'w, 's> {
+    pub(super) player_num: ResMut<'w, PlayerNumber>,
+    network_mapping: ResMut<'w, NetworkMapping>,
+    units_query: Query<
+        'w,
+        's,
+        (
+            &'static mut ArenaPos,
+            &'static mut Direction,
+            &'static mut UnitState,
+            &'static mut Health,
+            &'static mut Shield,
+            &'static mut ActiveEffects,
+        ),
+    >,
+    projectiles_query: Query<'w, 's, &'static mut ArenaPos, Without<UnitState>>,
+    towers: Query<'w, 's, &'static AssociatedTower>,
+    cmd: Commands<'w, 's>,
+}
+impl MessageHandler<'_, '_> {
+    pub(super) fn handle(&mut self, message: ServerMessage) {
+        let player_num = *self.player_num;
+        let cmd = &mut self.cmd;
         match message {
-            ServerMessage::StartGame(n) => *player_num = n,
+            ServerMessage::StartGame(n) => {
+                *self.player_num = n;
+                cmd.trigger(SendDeck);
+            }
             ServerMessage::SpawnUnit {
                 server_entity,
                 unit,
                 pos,
                 owner,
+                level,
             } => {
                 unit.spawn(
                     server_entity,
-                    pos.adjust_for_player(*player_num),
+                    pos.adjust_for_player(player_num),
                     owner,
-                    &mut cmd,
+                    level,
+                    cmd,
                 );
             }
             ServerMessage::SpawnProjectile {
@@ -97,40 +181,100 @@ fn handle_server_messages(
                 server_entity,
                 attacker,
                 receiver,
-                pos.adjust_for_player(*player_num),
-                &mut cmd,
+                pos.adjust_for_player(player_num),
+                cmd,
+            ),
+            ServerMessage::SpawnSpell {
+                server_entity,
+                spell,
+                pos,
+                target,
+            } => spell.spawn(
+                server_entity,
+                pos.adjust_for_player(player_num),
+                target.adjust_for_player(player_num),
+                cmd,
             ),
             ServerMessage::Despawn(server_entity) => {
-                let Some(entity) = network_mapping.remove(&server_entity) else {
-                    continue;
+                let Some(entity) = self.network_mapping.remove(&server_entity) else {
+                    return;
                 };
-                if let Ok(tower) = towers.get(entity) {
+                if let Ok(tower) = self.towers.get(entity) {
                     cmd.entity(tower.0).despawn();
                 }
-                cmd.entity(entity).despawn();
+                cmd.entity(entity).despawn_recursive();
+            }
+            ServerMessage::UnitDied(server_entity) => {
+                let Some(entity) = self.network_mapping.remove(&server_entity) else {
+                    return;
+                };
+                cmd.trigger(UnitDeath(entity));
+            }
+            ServerMessage::AddElixir(amount) => cmd.trigger(AddElixir(amount)),
+            ServerMessage::PlayRejected { card, elixir } => {
+                cmd.trigger(RejectPlay { card, elixir })
+            }
+            ServerMessage::ActivateKingTower(server_entity) => {
+                let Some(&entity) = self.network_mapping.get(&server_entity) else {
+                    return;
+                };
+                cmd.trigger(ActivateKingTower(entity));
+            }
+            ServerMessage::UpdateBalance { units, cards } => {
+                cmd.insert_resource(units);
+                cmd.insert_resource(cards);
             }
-            ServerMessage::SyncEntities { units, projectiles } => {
-                for (server_entity, pos, direction, state, health) in &units {
-                    let Some(&entity) = network_mapping.get(server_entity) else {
+            ServerMessage::SyncEntities {
+                units,
+                projectiles,
+                checksum,
+            } => {
+                for (server_entity, pos, direction, state, health, shield, effects) in &units {
+                    let Some(&entity) = self.network_mapping.get(server_entity) else {
                         continue;
                     };
-                    let (mut p, mut d, mut s, mut h) = units_query.get_mut(entity).unwrap();
-                    *p = pos.adjust_for_player(*player_num);
-                    *d = direction.adjust_for_player(*player_num);
+                    let (mut p, mut d, mut s, mut h, mut sh, mut e) =
+                        self.units_query.get_mut(entity).unwrap();
+                    *p = pos.adjust_for_player(player_num);
+                    *d = direction.adjust_for_player(player_num);
                     *s = *state;
                     *h = *health;
+                    *sh = *shield;
+                    // Цвет юнита перекрашивается только при изменении эффектов
+                    e.set_if_neq(*effects);
                 }
 
                 for (server_entity, pos) in &projectiles {
-                    let Some(&entity) = network_mapping.get(server_entity) else {
+                    let Some(&entity) = self.network_mapping.get(server_entity) else {
                         continue;
                     };
-                    let mut p = projectiles_query.get_mut(entity).unwrap();
-                    *p = pos.adjust_for_player(*player_num);
+                    let mut p = self.projectiles_query.get_mut(entity).unwrap();
+                    *p = pos.adjust_for_player(player_num);
+                }
+
+                if let Some(checksum) = checksum {
+                    let server = units
+                        .iter()
+                        .map(|&(entity, pos, _, state, health, ..)| {
+                            ChecksumEntry::new(entity, pos, state, health)
+                        })
+                        .collect();
+                    cmd.trigger(CheckSync { checksum, server });
                 }
             }
         }
     }
+
+    /// Убирает с арены всё, что пришло в сообщениях
+    pub(super) fn clear(&mut self) {
+        for (_, entity) in self.network_mapping.drain() {
+            // Юнит с башней мог уже исчезнуть вместе с экраном
+            if let Ok(tower) = self.towers.get(entity) {
+                self.cmd.entity(tower.0).try_despawn();
+            }
+            self.cmd.entity(entity).try_despawn_recursive();
+        }
+    }
 }
 
 #[derive(Resource, Reflect, Default, Deref, DerefMut)]
@@ -0,0 +1,150 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, utils::HashMap};
+use common::{
+    ArenaPos, Card, CardsData, ClientMessage, Level, PlayerNumber, ServerMessage, UnitsData,
+};
+use simulation::{Bot, CardPlay, Difficulty, HeadlessMatch, SimEvent, BOT_DECK, TICK};
+
+use crate::screens::GameState;
+
+use super::networking::{handle_server_messages, LocalChannel, MatchMode};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        OnEnter(GameState::Gameplay),
+        start_practice.run_if(resource_equals(MatchMode::Practice)),
+    );
+    app.add_systems(
+        Update,
+        run_local_server
+            .before(handle_server_messages)
+            .run_if(in_state(GameState::Gameplay).and(resource_exists::<LocalServer>)),
+    );
+    app.add_systems(OnExit(GameState::Gameplay), stop_practice);
+}
+
+/// В тренировке игрок всегда первый, бот занимает место второго
+const PLAYER: PlayerNumber = PlayerNumber::One;
+
+/// Сервер в том же процессе. Принимает ClientMessage и отвечает ServerMessage
+/// через LocalChannel так же, как настоящий сервер через QUIC
+#[derive(Resource)]
+struct LocalServer {
+    sim: HeadlessMatch,
+    bot: Bot,
+    decks: HashMap<PlayerNumber, HashMap<Card, Level>>,
+    // Время, накопленное до следующего тика
+    elapsed: Duration,
+}
+impl LocalServer {
+    /// Те же проверки колоды, эликсира и места, что и на сервере
+    fn play(
+        &mut self,
+        player: PlayerNumber,
+        card: Card,
+        placement: ArenaPos,
+    ) -> Result<(), String> {
+        let Some(&level) = self.decks.get(&player).and_then(|deck| deck.get(&card)) else {
+            return Err(format!("карта {card:?} не из колоды"));
+        };
+        let play = CardPlay {
+            tick: self.sim.tick() + 1,
+            player,
+            card,
+            level,
+            placement,
+        };
+        self.sim.check(&play)?;
+        self.sim.play(play);
+        Ok(())
+    }
+}
+
+fn start_practice(units: Res<UnitsData>, cards: Res<CardsData>, mut cmd: Commands) {
+    let seed = rand::random();
+    let mut sim = HeadlessMatch::new(units.clone(), cards.clone(), seed);
+    let bot = Bot::new(
+        PLAYER.opponent(),
+        BOT_DECK.to_vec(),
+        Difficulty::default(),
+        seed,
+    );
+    let bot_deck = BOT_DECK.iter().map(|&card| (card, Level::default()));
+
+    let mut channel = LocalChannel::default();
+    channel
+        .to_client
+        .push_back(ServerMessage::StartGame(PLAYER));
+    // Башни появились ещё при создании матча
+    for event in sim.events() {
+        forward(&event, &mut channel);
+    }
+
+    cmd.insert_resource(channel);
+    cmd.insert_resource(LocalServer {
+        sim,
+        bot,
+        decks: HashMap::from_iter([(PLAYER.opponent(), bot_deck.collect())]),
+        elapsed: Duration::ZERO,
+    });
+}
+
+fn stop_practice(mut cmd: Commands) {
+    cmd.remove_resource::<LocalServer>();
+    cmd.remove_resource::<LocalChannel>();
+}
+
+fn forward(event: &SimEvent, channel: &mut LocalChannel) {
+    if let Some((recipients, message)) = event.message() {
+        if recipients.includes(PLAYER) {
+            channel.to_client.push_back(message);
+        }
+    }
+}
+
+fn run_local_server(
+    mut server: ResMut<LocalServer>,
+    mut channel: ResMut<LocalChannel>,
+    time: Res<Time>,
+) {
+    let server = &mut *server;
+    while let Some(message) = channel.to_server.pop_front() {
+        match message {
+            ClientMessage::SetDeck(deck) => {
+                let deck = deck
+                    .into_iter()
+                    .map(|(card, level)| (card, Level::new(level.0)))
+                    .collect();
+                server.decks.insert(PLAYER, deck);
+            }
+            ClientMessage::PlayCard { card, placement } => {
+                let Err(e) = server.play(PLAYER, card, placement) else {
+                    continue;
+                };
+                warn!("Игрок {PLAYER:?}: {e}");
+                channel.to_client.push_back(ServerMessage::PlayRejected {
+                    card,
+                    elixir: server.sim.elixir(PLAYER),
+                });
+            }
+        }
+    }
+
+    // Как и сервер, снимок после каждого тика. Иначе за кадр в несколько тиков
+    // терялись бы снимки с контрольной суммой
+    server.elapsed += time.delta();
+    while server.elapsed >= TICK {
+        server.elapsed -= TICK;
+
+        if let Some((card, placement)) = server.sim.think(&mut server.bot) {
+            if let Err(e) = server.play(server.bot.player(), card, placement) {
+                warn!("Бот: {e}");
+            }
+        }
+        for event in server.sim.step() {
+            forward(&event, &mut channel);
+        }
+        channel.to_client.push_back(server.sim.snapshot());
+    }
+}
@@ -4,7 +4,7 @@ use bullet::SpawnBullet;
 use common::{ArenaPos, Projectile};
 use fireball::SpawnFireball;
 
-use crate::screens::GameState;
+use crate::screens::InMatch;
 
 use super::arena::ArenaHeightOffset;
 
@@ -15,10 +15,7 @@ mod fireball;
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins((bullet::plugin, fireball::plugin, bomb::plugin));
 
-    app.add_systems(
-        Update,
-        update_projectile_height.run_if(in_state(GameState::Gameplay)),
-    );
+    app.add_systems(Update, update_projectile_height.run_if(in_state(InMatch)));
 }
 
 pub(super) trait SpawnProjectile {
@@ -0,0 +1,336 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use bevy::prelude::*;
+use common::{balance_hash, CardsData, PlayerNumber, Replay, UnitsData, REPLAY_EXTENSION};
+use simulation::{HeadlessMatch, SimEvent, TICK};
+
+use crate::screens::{
+    ui::{OnPress, UiHitbox},
+    GameState,
+};
+
+use super::{networking::MessageHandler, spawn_text, FontAssets};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(GameState::Menu), find_replay);
+
+    app.add_systems(
+        OnEnter(GameState::Replay),
+        (
+            start_replay,
+            spawn_replay_hud.run_if(resource_exists::<ReplayPlayer>),
+        )
+            .chain(),
+    );
+    app.add_systems(
+        Update,
+        (replay_hotkeys, advance_replay, update_replay_hud)
+            .chain()
+            .run_if(in_state(GameState::Replay).and(resource_exists::<ReplayPlayer>)),
+    );
+    app.add_systems(OnExit(GameState::Replay), stop_replay);
+
+    app.add_observer(apply_replay_control);
+}
+
+/// Запись, которую покажет кнопка в меню
+#[derive(Resource)]
+pub struct ReplayFile(PathBuf);
+
+/// Папку с записями можно переопределить через BOYAR_REPLAYS.
+/// По умолчанию replays в рабочей папке игры, как и у сервера
+fn replays_dir() -> PathBuf {
+    env::var_os("BOYAR_REPLAYS")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("replays"))
+}
+
+fn is_replay(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == REPLAY_EXTENSION)
+}
+
+/// Файл можно передать аргументом командной строки, иначе берётся последняя запись
+fn find_replay(mut cmd: Commands) {
+    let from_args = env::args()
+        .skip(1)
+        .map(PathBuf::from)
+        .find(|p| is_replay(p));
+    let latest = || {
+        fs::read_dir(replays_dir())
+            .ok()?
+            .filter_map(Result::ok)
+            .filter(|entry| is_replay(&entry.path()))
+            .max_by_key(|entry| entry.metadata().and_then(|m| m.modified()).ok())
+            .map(|entry| entry.path())
+    };
+
+    match from_args.or_else(latest) {
+        Some(path) => cmd.insert_resource(ReplayFile(path)),
+        None => cmd.remove_resource::<ReplayFile>(),
+    }
+}
+
+const SPEEDS: [f32; 4] = [0.5, 1., 2., 4.];
+/// Шаг перемотки в секундах
+const SEEK_STEP: f32 = 10.;
+
+/// Матч из записи, который симулируется прямо в клиенте
+#[derive(Resource)]
+struct ReplayPlayer {
+    replay: Replay,
+    sim: HeadlessMatch,
+    paused: bool,
+    speed: f32,
+    // Время, накопленное до следующего тика
+    elapsed: Duration,
+    // Тик, до которого нужно пересчитать матч с начала
+    seek: Option<u64>,
+    perspective: PlayerNumber,
+}
+
+fn start_replay(
+    file: Option<Res<ReplayFile>>,
+    units: Res<UnitsData>,
+    cards: Res<CardsData>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut cmd: Commands,
+) {
+    let replay = file
+        .ok_or_else(|| "Нет записи для просмотра".to_string())
+        .and_then(|file| {
+            let bytes = fs::read(&file.0)
+                .map_err(|e| format!("Не удалось прочитать {}: {e}", file.0.display()))?;
+            Replay::decode(&bytes)
+        });
+    let replay = match replay {
+        Ok(replay) => replay,
+        Err(e) => {
+            error!("{e}");
+            next_state.set(GameState::Menu);
+            return;
+        }
+    };
+    // Матч всё равно показывается, но может разойтись с тем, что было на сервере
+    if replay.header.balance_hash != balance_hash(&units, &cards) {
+        warn!("Запись сделана на другой версии баланса");
+    }
+
+    let sim = HeadlessMatch::from_replay(&replay, units.clone(), cards.clone());
+    cmd.insert_resource(ReplayPlayer {
+        replay,
+        sim,
+        paused: false,
+        speed: 1.,
+        elapsed: Duration::ZERO,
+        seek: Some(0),
+        perspective: PlayerNumber::One,
+    });
+}
+
+fn stop_replay(mut handler: MessageHandler, mut cmd: Commands) {
+    handler.clear();
+    cmd.remove_resource::<ReplayPlayer>();
+}
+
+/// Сущность, о которой сообщает событие, пока она жива
+fn event_entity(event: &SimEvent) -> Option<Entity> {
+    match *event {
+        SimEvent::UnitSpawned { entity, .. }
+        | SimEvent::ProjectileSpawned { entity, .. }
+        | SimEvent::SpellSpawned { entity, .. }
+        | SimEvent::KingTowerActivated(entity) => Some(entity),
+        _ => None,
+    }
+}
+
+fn advance_replay(
+    mut player: ResMut<ReplayPlayer>,
+    units: Res<UnitsData>,
+    cards: Res<CardsData>,
+    time: Res<Time>,
+    mut handler: MessageHandler,
+) {
+    let player = &mut *player;
+    *handler.player_num = player.perspective;
+
+    if let Some(target) = player.seek.take() {
+        // Назад симуляцию не отмотать, поэтому матч пересчитывается с начала.
+        // Показываются только сущности, дожившие до нужного тика
+        handler.clear();
+        player.sim = HeadlessMatch::from_replay(&player.replay, units.clone(), cards.clone());
+        let mut alive = player.sim.events();
+        while player.sim.tick() < target {
+            for event in player.sim.step() {
+                match event {
+                    SimEvent::Despawned(entity) | SimEvent::UnitDied(entity) => {
+                        alive.retain(|e| event_entity(e) != Some(entity));
+                    }
+                    _ if event_entity(&event).is_some() => alive.push(event),
+                    _ => {}
+                }
+            }
+        }
+        for (_, message) in alive.iter().filter_map(SimEvent::message) {
+            handler.handle(message);
+        }
+        player.elapsed = Duration::ZERO;
+    }
+
+    if !player.paused {
+        player.elapsed += time.delta().mul_f32(player.speed);
+    }
+    while player.elapsed >= TICK && player.sim.tick() < player.replay.length {
+        player.elapsed -= TICK;
+        for event in player.sim.step() {
+            let Some((recipients, message)) = event.message() else {
+                continue;
+            };
+            if recipients.includes(player.perspective) {
+                handler.handle(message);
+            }
+        }
+    }
+    handler.handle(player.sim.snapshot());
+}
+
+/// Управление просмотром с кнопок и клавиатуры
+#[derive(Event, Clone, Copy)]
+enum ReplayControl {
+    TogglePause,
+    Speed(f32),
+    Seek(f32),
+    SwitchSide,
+    Exit,
+}
+
+fn apply_replay_control(
+    trigger: Trigger<ReplayControl>,
+    player: Option<ResMut<ReplayPlayer>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Some(mut player) = player else {
+        return;
+    };
+    match *trigger.event() {
+        ReplayControl::TogglePause => player.paused ^= true,
+        ReplayControl::Speed(speed) => player.speed = speed,
+        ReplayControl::Seek(secs) => {
+            let current = player.seek.unwrap_or(player.sim.tick()) as f32;
+            let target = current + secs / TICK.as_secs_f32();
+            player.seek = Some(target.clamp(0., player.replay.length as f32) as u64);
+        }
+        ReplayControl::SwitchSide => {
+            player.perspective = player.perspective.opponent();
+            // Все сущности нужно создать заново с другой стороны арены
+            player.seek = Some(player.seek.unwrap_or(player.sim.tick()));
+        }
+        ReplayControl::Exit => next_state.set(GameState::Menu),
+    }
+}
+
+fn replay_hotkeys(keyboard: Res<ButtonInput<KeyCode>>, mut cmd: Commands) {
+    let bindings = [
+        (KeyCode::Space, ReplayControl::TogglePause),
+        (KeyCode::Digit1, ReplayControl::Speed(SPEEDS[0])),
+        (KeyCode::Digit2, ReplayControl::Speed(SPEEDS[1])),
+        (KeyCode::Digit3, ReplayControl::Speed(SPEEDS[2])),
+        (KeyCode::Digit4, ReplayControl::Speed(SPEEDS[3])),
+        (KeyCode::ArrowLeft, ReplayControl::Seek(-SEEK_STEP)),
+        (KeyCode::ArrowRight, ReplayControl::Seek(SEEK_STEP)),
+        (KeyCode::Tab, ReplayControl::SwitchSide),
+        (KeyCode::Escape, ReplayControl::Exit),
+    ];
+    for (key, control) in bindings {
+        if keyboard.just_pressed(key) {
+            cmd.trigger(control);
+        }
+    }
+}
+
+/// Строка с паузой, скоростью, временем и стороной
+#[derive(Component)]
+struct ReplayStatus;
+
+fn spawn_replay_hud(mut cmd: Commands, font: Res<FontAssets>) {
+    let status = spawn_text(
+        &mut cmd,
+        "",
+        font.font.clone(),
+        30.,
+        Color::WHITE,
+        1.,
+        (0., -5.3),
+        GameState::Replay,
+    );
+    for entity in status {
+        cmd.entity(entity).insert(ReplayStatus);
+    }
+
+    let buttons = [
+        ("-10с", (-3.3, -6.3), ReplayControl::Seek(-SEEK_STEP)),
+        ("Пауза", (-1.1, -6.3), ReplayControl::TogglePause),
+        ("+10с", (1.1, -6.3), ReplayControl::Seek(SEEK_STEP)),
+        ("Сторона", (3.3, -6.3), ReplayControl::SwitchSide),
+        ("x0.5", (-3.6, -7.3), ReplayControl::Speed(SPEEDS[0])),
+        ("x1", (-2.2, -7.3), ReplayControl::Speed(SPEEDS[1])),
+        ("x2", (-0.8, -7.3), ReplayControl::Speed(SPEEDS[2])),
+        ("x4", (0.6, -7.3), ReplayControl::Speed(SPEEDS[3])),
+        ("Меню", (3.3, -7.3), ReplayControl::Exit),
+    ];
+    for (text, pos, control) in buttons {
+        let [text, _] = spawn_text(
+            &mut cmd,
+            text,
+            font.font.clone(),
+            30.,
+            Color::srgb(1., 1., 0.),
+            1.,
+            pos,
+            GameState::Replay,
+        );
+        cmd.entity(text)
+            .insert((control, UiHitbox(1.3, 0.8)))
+            .observe(on_replay_button);
+    }
+}
+
+fn on_replay_button(
+    trigger: Trigger<OnPress>,
+    buttons: Query<&ReplayControl>,
+    mut cmd: Commands,
+) {
+    if let Ok(&control) = buttons.get(trigger.entity()) {
+        cmd.trigger(control);
+    }
+}
+
+fn format_time(tick: u64) -> String {
+    let secs = (tick as f32 * TICK.as_secs_f32()) as u64;
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+fn update_replay_hud(
+    player: Res<ReplayPlayer>,
+    mut status: Query<&mut Text2d, With<ReplayStatus>>,
+) {
+    let state = match player.paused {
+        true => "Пауза".to_string(),
+        false => format!("x{}", player.speed),
+    };
+    let side = match player.perspective {
+        PlayerNumber::One => 1,
+        PlayerNumber::Two => 2,
+    };
+    let text = format!(
+        "{state}  {} / {}  Игрок {side}",
+        format_time(player.sim.tick()),
+        format_time(player.replay.length),
+    );
+    for mut status in &mut status {
+        status.0.clone_from(&text);
+    }
+}
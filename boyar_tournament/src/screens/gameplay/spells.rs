@@ -0,0 +1,128 @@
+use bevy::prelude::*;
+use bevy_aseprite_ultra::prelude::*;
+use common::{ArenaPos, CardsData, Spell, SpellSprite};
+
+use crate::{scaling::DynamicScale, screens::InMatch};
+
+use super::{arena::ArenaHeightOffset, networking::NetworkMapping, units::UnitSprites};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(spawn_spell);
+
+    app.add_systems(OnEnter(InMatch), load_spell_sprites);
+    app.add_systems(Update, update_spell_height.run_if(in_state(InMatch)));
+}
+
+pub(super) trait SpawnSpell {
+    fn spawn(&self, entity: Entity, pos: ArenaPos, target: ArenaPos, cmd: &mut Commands);
+}
+
+impl SpawnSpell for Spell {
+    fn spawn(&self, entity: Entity, pos: ArenaPos, target: ArenaPos, cmd: &mut Commands) {
+        cmd.trigger(SpawnSpellEvent(entity, *self, pos, target));
+    }
+}
+
+#[derive(Event)]
+struct SpawnSpellEvent(Entity, Spell, ArenaPos, ArenaPos);
+
+fn load_spell_sprites(
+    cards: Res<CardsData>,
+    mut sprites: ResMut<UnitSprites>,
+    asset_server: Res<AssetServer>,
+) {
+    for stats in cards.values() {
+        if let Some(spell) = &stats.spell {
+            sprites.get(spell.view.sprite.path(), &asset_server);
+        }
+    }
+}
+
+/// Все заклинания собираются из описания в cards.ron
+fn spawn_spell(
+    trigger: Trigger<SpawnSpellEvent>,
+    mut cmd: Commands,
+    cards: Res<CardsData>,
+    mut sprites: ResMut<UnitSprites>,
+    asset_server: Res<AssetServer>,
+    mut network_mapping: ResMut<NetworkMapping>,
+) {
+    let &SpawnSpellEvent(entity, spell, pos, target) = trigger.event();
+    let view = &cards.spell(spell).view;
+    let aseprite = sprites.get(view.sprite.path(), &asset_server);
+
+    let mut spell_entity = cmd.spawn((
+        spell,
+        Name::new(view.name.clone()),
+        pos,
+        DynamicScale(1.),
+        ArenaHeightOffset(0.),
+        SpellArc {
+            start: pos,
+            target,
+            peak: view.peak,
+        },
+    ));
+    let color = match view.tint {
+        Some((r, g, b)) => Color::srgb(r, g, b),
+        None => Color::WHITE,
+    };
+    match &view.sprite {
+        SpellSprite::Animation { tag, .. } => {
+            let animation = match tag {
+                Some(tag) => Animation::tag(tag),
+                None => Animation::default(),
+            };
+            spell_entity.insert((
+                AseSpriteAnimation {
+                    animation,
+                    aseprite,
+                },
+                Sprite { color, ..default() },
+            ));
+        }
+        SpellSprite::Volley { slice, offsets, .. } => {
+            spell_entity
+                .insert((Transform::default(), Visibility::default()))
+                .with_children(|volley| {
+                    for &(x, y) in offsets {
+                        volley.spawn((
+                            AseSpriteSlice {
+                                name: slice.clone(),
+                                aseprite: aseprite.clone(),
+                            },
+                            Sprite { color, ..default() },
+                            Transform::from_xyz(x, y, 0.),
+                        ));
+                    }
+                });
+        }
+    }
+
+    network_mapping.insert(entity, spell_entity.id());
+}
+
+/// Заклинание летит по дуге с башни короля до точки розыгрыша
+#[derive(Component)]
+struct SpellArc {
+    start: ArenaPos,
+    target: ArenaPos,
+    // Наибольшая высота над прямой траекторией
+    peak: f32,
+}
+
+// Высота короля над башней
+const START_HEIGHT: f32 = 3.;
+
+fn update_spell_height(mut spells: Query<(&ArenaPos, &SpellArc, &mut ArenaHeightOffset)>) {
+    for (pos, arc, mut height) in &mut spells {
+        let total = arc.start.distance(&arc.target);
+        if total < 0.01 {
+            height.0 = 0.;
+            continue;
+        }
+        let progress = (arc.start.distance(pos) / total).clamp(0., 1.);
+
+        height.0 = START_HEIGHT * (1. - progress) + 4. * arc.peak * progress * (1. - progress);
+    }
+}
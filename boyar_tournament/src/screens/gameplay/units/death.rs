@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+use common::UnitState;
+
+use crate::screens::{gameplay::arena::ArenaHeightOffset, InMatch};
+
+use super::AssociatedTower;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(start_dying);
+
+    app.add_systems(Update, update_dying.run_if(in_state(InMatch)));
+}
+
+/// Сервер сообщил о гибели юнита
+#[derive(Event)]
+pub struct UnitDeath(pub Entity);
+
+/// Анимация гибели: юнит бледнеет и опускается, затем исчезает вместе с башней.
+/// Хранит исходную высоту юнита
+#[derive(Component)]
+pub(super) struct Dying(Timer, f32);
+
+fn start_dying(
+    trigger: Trigger<UnitDeath>,
+    mut units: Query<(&mut UnitState, Option<&ArenaHeightOffset>)>,
+    mut cmd: Commands,
+) {
+    let UnitDeath(unit) = *trigger.event();
+    let Ok((mut state, height)) = units.get_mut(unit) else {
+        return;
+    };
+
+    // Замираем на текущем кадре
+    *state = UnitState::Idle;
+    let height = height.map_or(0., |height| height.0);
+    cmd.entity(unit)
+        .insert(Dying(Timer::from_seconds(0.5, TimerMode::Once), height));
+}
+
+fn update_dying(
+    mut units: Query<(
+        Entity,
+        &mut Dying,
+        &mut Sprite,
+        Option<&mut ArenaHeightOffset>,
+        Option<&AssociatedTower>,
+    )>,
+    mut towers: Query<&mut Sprite, Without<Dying>>,
+    time: Res<Time>,
+    mut cmd: Commands,
+) {
+    for (unit, mut dying, mut sprite, height, tower) in &mut units {
+        let progress = dying.0.tick(time.delta()).fraction();
+
+        let color = Color::srgba(0.5, 0.5, 0.5, 1. - progress);
+        sprite.color = color;
+        if let Some(mut height) = height {
+            height.0 = dying.1 - progress * 0.5;
+        }
+        if let Some(mut tower_sprite) = tower.and_then(|tower| towers.get_mut(tower.0).ok()) {
+            tower_sprite.color = color;
+        }
+
+        if dying.0.finished() {
+            if let Some(tower) = tower {
+                cmd.entity(tower.0).despawn();
+            }
+            cmd.entity(unit).despawn_recursive();
+        }
+    }
+}
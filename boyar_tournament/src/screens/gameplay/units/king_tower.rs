@@ -1,110 +1,66 @@
-use bevy::prelude::*;
-use bevy_aseprite_ultra::prelude::*;
-use bevy_asset_loader::prelude::*;
-use common::{ArenaPos, Health, PlayerNumber, UnitState};
+use std::f32::consts::PI;
 
-use crate::{
-    scaling::DynamicScale,
-    screens::{
-        gameplay::{arena::ArenaHeightOffset, networking::NetworkMapping},
-        GameState,
-    },
-};
+use bevy::prelude::*;
 
-use super::{AssociatedTower, IntoTag, SpawnDirection};
+use crate::screens::{gameplay::arena::ArenaHeightOffset, InMatch};
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_observer(spawn_king_tower);
+    app.add_observer(tint_dormant_king);
+    app.add_observer(activate_king_tower);
 
-    app.configure_loading_state(
-        LoadingStateConfig::new(GameState::Loading).load_collection::<KingTowerAssets>(),
-    );
-
-    app.add_systems(OnExit(GameState::Gameplay), despawn_king_towers);
+    app.add_systems(Update, update_wake_up.run_if(in_state(InMatch)));
 }
 
+/// Сервер сообщил, что башня короля начала атаковать
 #[derive(Event)]
-pub struct SpawnKingTower(pub Entity, pub ArenaPos, pub PlayerNumber);
+pub struct ActivateKingTower(pub Entity);
 
-#[derive(Component)]
-#[require(
-    DynamicScale(|| DynamicScale(0.75)),
-    ArenaHeightOffset(|| ArenaHeightOffset(1.3)),
-)]
-struct KingTower;
+const DORMANT_KING_COLOR: Color = Color::srgb(0.5, 0.5, 0.6);
 
+/// Король спит, пока башня не активирована
 #[derive(Component)]
-#[require(
-    Health,
+pub(super) struct Dormant;
 
-    Name(|| Name::new("Король на башне")),
-    DynamicScale(|| DynamicScale(0.55)),
-    UnitState,
-    ArenaHeightOffset(|| ArenaHeightOffset(3.)),
-)]
-struct KingTowerKing;
-
-#[derive(Resource, AssetCollection)]
-struct KingTowerAssets {
-    #[asset(path = "units/king_tower/ally_tower.aseprite")]
-    ally_tower: Handle<Aseprite>,
-    #[asset(path = "units/king_tower/enemy_tower.aseprite")]
-    enemy_tower: Handle<Aseprite>,
+/// Анимация пробуждения: король подпрыгивает и возвращает себе цвет.
+/// Хранит исходную высоту короля над башней
+#[derive(Component)]
+struct WakeUp(Timer, f32);
 
-    #[asset(path = "units/priest/ally_priest.aseprite")]
-    ally_king: Handle<Aseprite>,
-    #[asset(path = "units/priest/enemy_priest.aseprite")]
-    enemy_king: Handle<Aseprite>,
+fn tint_dormant_king(trigger: Trigger<OnAdd, Dormant>, mut kings: Query<&mut Sprite>) {
+    if let Ok(mut sprite) = kings.get_mut(trigger.entity()) {
+        sprite.color = DORMANT_KING_COLOR;
+    }
 }
 
-fn spawn_king_tower(
-    trigger: Trigger<SpawnKingTower>,
+fn activate_king_tower(
+    trigger: Trigger<ActivateKingTower>,
+    kings: Query<&ArenaHeightOffset, With<Dormant>>,
     mut cmd: Commands,
-    self_num: Res<PlayerNumber>,
-    assets: ResMut<KingTowerAssets>,
-    mut network_mapping: ResMut<NetworkMapping>,
 ) {
-    let SpawnKingTower(entity, mut pos, player_num) = trigger.event();
-
-    let direction = self_num.spawn_direction(*player_num);
-
-    let (tower_sprite, king_sprite) = if pos.1 < 0. {
-        (assets.ally_tower.clone(), assets.ally_king.clone())
-    } else {
-        (assets.enemy_tower.clone(), assets.enemy_king.clone())
+    let &ActivateKingTower(king) = trigger.event();
+    let Ok(height) = kings.get(king) else {
+        return;
     };
 
-    pos.1 += 0.01;
-    let tower = cmd
-        .spawn((
-            KingTower,
-            pos,
-            AseSpriteSlice {
-                name: "king_tower".into(),
-                aseprite: tower_sprite,
-            },
-        ))
-        .id();
-
-    pos.1 -= 0.01;
-    let king = cmd
-        .spawn((
-            KingTowerKing,
-            pos,
-            direction,
-            AseSpriteAnimation {
-                animation: Animation::tag(direction.tag()),
-                aseprite: king_sprite,
-            },
-            AssociatedTower(tower),
-        ))
-        .id();
-    network_mapping.insert(*entity, king);
+    cmd.entity(king)
+        .remove::<Dormant>()
+        .insert(WakeUp(Timer::from_seconds(0.6, TimerMode::Once), height.0));
 }
 
-fn despawn_king_towers(mut cmd: Commands, towers: Query<(Entity, &AssociatedTower)>) {
-    for (king, tower) in towers.iter() {
-        cmd.entity(tower.0).despawn();
-        cmd.entity(king).despawn();
+fn update_wake_up(
+    mut kings: Query<(Entity, &mut WakeUp, &mut Sprite, &mut ArenaHeightOffset)>,
+    time: Res<Time>,
+    mut cmd: Commands,
+) {
+    for (king, mut wake_up, mut sprite, mut height) in &mut kings {
+        let progress = wake_up.0.tick(time.delta()).fraction();
+
+        sprite.color = DORMANT_KING_COLOR.mix(&Color::WHITE, progress);
+        height.0 = wake_up.1 + (progress * PI).sin() * 0.7;
+
+        if wake_up.0.finished() {
+            height.0 = wake_up.1;
+            cmd.entity(king).remove::<WakeUp>();
+        }
     }
 }
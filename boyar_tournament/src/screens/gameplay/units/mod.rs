@@ -1,46 +1,77 @@
-use archer_tower::SpawnArcherTower;
-use bat::SpawnBat;
-use bevy::prelude::*;
-use bevy_aseprite_ultra::prelude::{AnimationState, AseSpriteAnimation, Aseprite};
-use bomber::SpawnBomber;
-use common::{ArenaPos, Direction, Health, PlayerNumber, Unit, UnitState};
-use king_tower::SpawnKingTower;
-use musketeer::SpawnMusketeer;
-use priest::SpawnPriest;
-use rus::SpawnRus;
-use giant::SpawnGiant;
-
-use crate::screens::GameState;
-
-mod archer_tower;
-mod bat;
-mod bomber;
+use bevy::{prelude::*, utils::HashMap};
+use bevy_aseprite_ultra::prelude::*;
+use common::{
+    ActiveEffects, ArenaPos, Direction, Health, Level, PlayerNumber, Shield, SpriteLayer,
+    SpriteView, StatusEffect, TowerKind, Unit, UnitState, UnitsData,
+};
+use death::Dying;
+pub use death::UnitDeath;
+pub use king_tower::ActivateKingTower;
+use king_tower::Dormant;
+
+use crate::{
+    scaling::{DrawRegion, DynamicScale},
+    screens::InMatch,
+};
+
+use super::{arena::ArenaHeightOffset, networking::NetworkMapping, FontAssets};
+
+mod death;
 mod king_tower;
-mod musketeer;
-mod priest;
-mod rus;
-mod giant;
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<Direction>();
     app.register_type::<UnitState>();
     app.register_type::<Health>();
+    app.register_type::<ActiveEffects>();
+    app.register_type::<Level>();
+    app.register_type::<Shield>();
 
+    app.init_resource::<UnitSprites>();
+    app.add_observer(spawn_unit);
+
+    app.add_systems(OnEnter(InMatch), load_unit_sprites);
+    app.add_systems(PreUpdate, manage_animation.run_if(in_state(InMatch)));
     app.add_systems(
-        PreUpdate,
-        manage_animation.run_if(in_state(GameState::Gameplay)),
+        Update,
+        (
+            tint_status_effects,
+            update_deploy_countdowns,
+            draw_health_bars,
+            update_level_labels,
+        )
+            .run_if(in_state(InMatch)),
     );
+    app.add_systems(OnExit(InMatch), despawn_towers);
 
-    app.add_plugins((
-        archer_tower::plugin,
-        king_tower::plugin,
-        rus::plugin,
-        musketeer::plugin,
-        bat::plugin,
-        priest::plugin,
-        bomber::plugin,
-        giant::plugin,
-    ));
+    app.add_plugins((death::plugin, king_tower::plugin));
+}
+
+/// Спрайты юнитов из units.ron и заклинаний из cards.ron, загружаются заранее
+#[derive(Resource, Default)]
+pub(super) struct UnitSprites(HashMap<String, Handle<Aseprite>>);
+impl UnitSprites {
+    pub(super) fn get(&mut self, path: &str, asset_server: &AssetServer) -> Handle<Aseprite> {
+        self.0
+            .entry(path.into())
+            .or_insert_with(|| asset_server.load(path.to_owned()))
+            .clone()
+    }
+}
+
+fn load_unit_sprites(
+    units: Res<UnitsData>,
+    mut sprites: ResMut<UnitSprites>,
+    asset_server: Res<AssetServer>,
+) {
+    for stats in units.values() {
+        let layers = [Some(&stats.view.sprite), stats.view.tower.as_ref()];
+        for layer in layers.into_iter().flatten() {
+            for path in layer.source.paths() {
+                sprites.get(path, &asset_server);
+            }
+        }
+    }
 }
 
 fn manage_animation(
@@ -53,26 +84,20 @@ fn manage_animation(
     aseprites: Res<Assets<Aseprite>>,
 ) {
     for (direction, state, mut animation, mut animation_state) in animation_query.iter_mut() {
+        // Спрайты юнитов грузятся после экрана загрузки
+        let Some(aseprite) = aseprites.get(animation.aseprite.id()) else {
+            continue;
+        };
         match state {
-            UnitState::Idle => {
-                let tag_meta = aseprites
-                    .get(animation.aseprite.id())
-                    .unwrap()
-                    .tags
-                    .get(direction.tag())
-                    .unwrap();
+            UnitState::Idle | UnitState::Deploying => {
+                let tag_meta = aseprite.tags.get(direction.tag()).unwrap();
                 let start_frame = tag_meta.range.start();
                 animation_state.current_frame = *start_frame;
 
                 animation.animation.tag = Some(direction.tag().into());
             }
-            UnitState::Moving => {
-                let tag_meta = aseprites
-                    .get(animation.aseprite.id())
-                    .unwrap()
-                    .tags
-                    .get(direction.tag())
-                    .unwrap();
+            UnitState::Moving | UnitState::Charging => {
+                let tag_meta = aseprite.tags.get(direction.tag()).unwrap();
                 let start_frame = tag_meta.range.start();
                 let end_frame = tag_meta.range.end();
                 if animation_state.current_frame < *start_frame
@@ -83,16 +108,12 @@ fn manage_animation(
 
                 animation.animation.tag = Some(direction.tag().into());
             }
-            UnitState::Attacking => {
+            // Рывок анимируется как атака, удар наносится при приземлении
+            UnitState::Attacking | UnitState::Dashing => {
                 let mut tag = String::from(direction.tag());
                 tag.push('a');
 
-                let tag_meta = aseprites
-                    .get(animation.aseprite.id())
-                    .unwrap()
-                    .tags
-                    .get(&tag)
-                    .unwrap();
+                let tag_meta = aseprite.tags.get(&tag).unwrap();
                 let start_frame = tag_meta.range.start();
                 let end_frame = tag_meta.range.end();
                 if animation_state.current_frame < *start_frame
@@ -104,6 +125,156 @@ fn manage_animation(
                 animation.animation.tag = Some(tag);
             }
         }
+
+        // Разогнавшийся юнит перебирает ногами быстрее
+        animation.animation.speed = match state {
+            UnitState::Charging => 1.6,
+            _ => 1.,
+        };
+    }
+}
+
+fn tint_status_effects(mut query: Query<(Ref<ActiveEffects>, &mut Sprite)>) {
+    for (effects, mut sprite) in &mut query {
+        // При спавне цвет задаёт сам юнит
+        if !effects.is_changed() || effects.is_added() {
+            continue;
+        }
+
+        sprite.color = if effects.contains(StatusEffect::Freeze) {
+            Color::srgb(0.55, 0.8, 1.)
+        } else if effects.contains(StatusEffect::Stun) {
+            Color::srgb(1., 1., 0.55)
+        } else if effects.contains(StatusEffect::Rage) {
+            Color::srgb(1., 0.55, 1.)
+        } else if effects.contains(StatusEffect::Slow) {
+            Color::srgb(0.7, 0.7, 1.)
+        } else {
+            Color::WHITE
+        };
+    }
+}
+
+/// Отсчёт времени развёртывания, отображается кольцом под юнитом
+#[derive(Component)]
+pub struct DeployCountdown {
+    unit: Unit,
+    elapsed: f32,
+}
+impl DeployCountdown {
+    pub fn new(unit: Unit) -> Self {
+        Self { unit, elapsed: 0. }
+    }
+}
+
+fn update_deploy_countdowns(
+    mut query: Query<(Entity, &mut DeployCountdown, &ArenaPos)>,
+    units: Res<UnitsData>,
+    draw_region: Res<DrawRegion>,
+    time: Res<Time>,
+    mut gizmos: Gizmos,
+    mut cmd: Commands,
+) {
+    for (entity, mut countdown, pos) in &mut query {
+        countdown.elapsed += time.delta_secs();
+        let deploy_time = units.stats(countdown.unit).deploy_time;
+        if countdown.elapsed >= deploy_time {
+            cmd.entity(entity).remove::<DeployCountdown>();
+            continue;
+        }
+
+        // Положение на арене без учёта ArenaHeightOffset, то есть под ногами юнита
+        let center = Vec2::new(
+            pos.0 * draw_region.width / 19.61,
+            pos.1 * draw_region.height / 43.2 + draw_region.height / 13.5,
+        );
+        let radius = 0.7 * draw_region.width / 19.61;
+        let remaining = 1. - countdown.elapsed / deploy_time;
+
+        gizmos.circle_2d(center, radius, Color::srgba(1., 1., 1., 0.3));
+        gizmos.arc_2d(
+            Isometry2d::from_translation(center),
+            remaining * std::f32::consts::TAU,
+            radius,
+            Color::srgb(1., 0.85, 0.2),
+        );
+    }
+}
+
+/// Ширина полоски здоровья в клетках арены
+const HEALTH_BAR_WIDTH: f32 = 1.4;
+
+/// Середина полоски здоровья: над спрайтом юнита
+fn health_bar_center(
+    transform: &Transform,
+    height: &ArenaHeightOffset,
+    draw_region: &DrawRegion,
+) -> Vec2 {
+    let offset = (height.0 + 1.) * draw_region.height / 43.2;
+    transform.translation.truncate() + Vec2::new(0., offset)
+}
+
+fn draw_health_bars(
+    units: Query<
+        (
+            &Transform,
+            &ArenaHeightOffset,
+            &Health,
+            &Shield,
+            &PlayerNumber,
+        ),
+        (With<Unit>, Without<Dying>),
+    >,
+    self_num: Res<PlayerNumber>,
+    draw_region: Res<DrawRegion>,
+    mut gizmos: Gizmos,
+) {
+    let width = HEALTH_BAR_WIDTH * draw_region.width / 19.61;
+    for (transform, height, health, shield, player_num) in &units {
+        let center = health_bar_center(transform, height, &draw_region);
+        let left = center - Vec2::new(width / 2., 0.);
+        let fraction = health.0 as f32 / health.1.max(1) as f32;
+        let color = match *player_num == *self_num {
+            true => Color::srgb(0.3, 0.6, 1.),
+            false => Color::srgb(1., 0.3, 0.3),
+        };
+
+        gizmos.line_2d(
+            left,
+            left + Vec2::new(width, 0.),
+            Color::srgba(0., 0., 0., 0.6),
+        );
+        gizmos.line_2d(left, left + Vec2::new(width * fraction, 0.), color);
+
+        // Щит рисуется поверх полоски здоровья, пока он не сломан
+        if shield.0 > 0 {
+            let left = left + Vec2::new(0., 0.25 * draw_region.height / 43.2);
+            let fraction = shield.0 as f32 / shield.1.max(1) as f32;
+            let color = Color::srgb(0.85, 0.85, 0.95);
+            gizmos.line_2d(left, left + Vec2::new(width * fraction, 0.), color);
+        }
+    }
+}
+
+/// Уровень юнита слева от его полоски здоровья
+#[derive(Component)]
+struct LevelLabel(Entity);
+
+fn update_level_labels(
+    mut labels: Query<(Entity, &LevelLabel, &mut Transform)>,
+    units: Query<(&Transform, &ArenaHeightOffset), (Without<LevelLabel>, Without<Dying>)>,
+    draw_region: Res<DrawRegion>,
+    mut cmd: Commands,
+) {
+    for (label, &LevelLabel(unit), mut transform) in &mut labels {
+        let Ok((unit_transform, height)) = units.get(unit) else {
+            // Юнит погиб или погибает
+            cmd.entity(label).despawn();
+            continue;
+        };
+        let center = health_bar_center(unit_transform, height, &draw_region);
+        let offset = (HEALTH_BAR_WIDTH / 2. + 0.3) * draw_region.width / 19.61;
+        transform.translation = (center - Vec2::new(offset, 0.)).extend(1.);
     }
 }
 
@@ -111,12 +282,131 @@ fn manage_animation(
 #[derive(Component)]
 pub struct AssociatedTower(pub Entity);
 
+#[derive(Event)]
+pub struct SpawnUnitEvent(
+    pub Entity,
+    pub Unit,
+    pub ArenaPos,
+    pub PlayerNumber,
+    pub Level,
+);
+
+pub(super) fn insert_sprite(
+    entity: &mut EntityCommands,
+    layer: &SpriteLayer,
+    ally: bool,
+    direction: Direction,
+    sprites: &mut UnitSprites,
+    asset_server: &AssetServer,
+) {
+    let aseprite = sprites.get(layer.source.path(ally), asset_server);
+    entity.insert((DynamicScale(layer.scale), ArenaHeightOffset(layer.height)));
+    match &layer.source {
+        SpriteView::Animation(_) | SpriteView::SidedAnimation { .. } => {
+            entity.insert(AseSpriteAnimation {
+                animation: Animation::tag(direction.tag()),
+                aseprite,
+            })
+        }
+        SpriteView::SidedSlice { slice, .. } => entity.insert(AseSpriteSlice {
+            name: slice.clone(),
+            aseprite,
+        }),
+    };
+}
+
+/// Все юниты собираются из описания в units.ron
+fn spawn_unit(
+    trigger: Trigger<SpawnUnitEvent>,
+    mut cmd: Commands,
+    self_num: Res<PlayerNumber>,
+    units: Res<UnitsData>,
+    mut sprites: ResMut<UnitSprites>,
+    asset_server: Res<AssetServer>,
+    font: Res<FontAssets>,
+    mut network_mapping: ResMut<NetworkMapping>,
+) {
+    let &SpawnUnitEvent(entity, unit, mut pos, player_num, level) = trigger.event();
+    let stats = units.stats(unit);
+    let view = &stats.view;
+
+    let ally = player_num == *self_num;
+    let direction = self_num.spawn_direction(player_num);
+
+    // Башня чуть выше юнита, чтобы рисоваться позади него
+    let tower = view.tower.as_ref().map(|layer| {
+        pos.1 += 0.01;
+        let mut tower = cmd.spawn((Name::new("Башня"), pos));
+        insert_sprite(
+            &mut tower,
+            layer,
+            ally,
+            direction,
+            &mut sprites,
+            &asset_server,
+        );
+        pos.1 -= 0.01;
+        tower.id()
+    });
+
+    let mut unit_entity = cmd.spawn((
+        unit,
+        Name::new(view.name.clone()),
+        pos,
+        direction,
+        player_num,
+        level,
+        Health::default(),
+        Shield::default(),
+        UnitState::default(),
+        ActiveEffects::default(),
+    ));
+    insert_sprite(
+        &mut unit_entity,
+        &view.sprite,
+        ally,
+        direction,
+        &mut sprites,
+        &asset_server,
+    );
+    if let Some(tower) = tower {
+        unit_entity.insert(AssociatedTower(tower));
+    }
+    if stats.deploy_time > 0. {
+        unit_entity.insert(DeployCountdown::new(unit));
+    }
+    if let Some(TowerKind::King) = stats.tower {
+        unit_entity.insert(Dormant);
+    }
+
+    let unit_entity = unit_entity.id();
+    cmd.spawn((
+        Name::new("Уровень"),
+        LevelLabel(unit_entity),
+        Text2d::new(level.0.to_string()),
+        TextFont::from_font(font.font.clone()).with_font_size(30.),
+        TextColor(Color::WHITE),
+        DynamicScale(0.5),
+        StateScoped(InMatch),
+    ));
+
+    network_mapping.insert(entity, unit_entity);
+}
+
+fn despawn_towers(mut cmd: Commands, towers: Query<(Entity, &AssociatedTower)>) {
+    for (unit, tower) in towers.iter() {
+        cmd.entity(tower.0).despawn();
+        cmd.entity(unit).despawn();
+    }
+}
+
 pub(super) trait SpawnUnit {
     fn spawn(
         &self,
         entity: Entity,
         pos: ArenaPos,
         player_num: PlayerNumber,
+        level: Level,
         cmd: &mut Commands,
     );
 }
@@ -127,18 +417,10 @@ impl SpawnUnit for Unit {
         entity: Entity,
         pos: ArenaPos,
         player_num: PlayerNumber,
+        level: Level,
         cmd: &mut Commands,
     ) {
-        match self {
-            Unit::ArcherTower => cmd.trigger(SpawnArcherTower(entity, pos, player_num)),
-            Unit::KingTower => cmd.trigger(SpawnKingTower(entity, pos, player_num)),
-            Unit::Rus => cmd.trigger(SpawnRus(entity, pos, player_num)),
-            Unit::Musketeer => cmd.trigger(SpawnMusketeer(entity, pos, player_num)),
-            Unit::Bat => cmd.trigger(SpawnBat(entity, pos, player_num)),
-            Unit::Priest => cmd.trigger(SpawnPriest(entity, pos, player_num)),
-            Unit::Bomber => cmd.trigger(SpawnBomber(entity, pos, player_num)),
-            Unit::Giant => cmd.trigger(SpawnGiant(entity, pos, player_num)),
-        }
+        cmd.trigger(SpawnUnitEvent(entity, *self, pos, player_num, level));
     }
 }
 
@@ -172,8 +454,11 @@ impl IntoTag for UnitState {
     fn tag(&self) -> &'static str {
         match self {
             UnitState::Idle => "",
+            UnitState::Deploying => "",
             UnitState::Moving => "",
             UnitState::Attacking => "a",
+            UnitState::Charging => "",
+            UnitState::Dashing => "a",
         }
     }
 }
@@ -7,7 +7,7 @@ use super::GameState;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_loading_state(
-        LoadingState::new(GameState::Loading).continue_to_state(GameState::Gameplay),
+        LoadingState::new(GameState::Loading).continue_to_state(GameState::Menu),
     );
 
     app.add_systems(OnEnter(GameState::Loading), spawn_loading_screen);
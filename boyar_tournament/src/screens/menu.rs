@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+
+use super::{
+    gameplay::{spawn_text, FontAssets, MatchMode, ReplayFile},
+    ui::{OnPress, UiHitbox},
+    GameState,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(GameState::Menu), spawn_menu);
+}
+
+fn spawn_menu(mut cmd: Commands, font: Res<FontAssets>, replay: Option<Res<ReplayFile>>) {
+    spawn_text(
+        &mut cmd,
+        "Боярский Турнир",
+        font.font.clone(),
+        60.,
+        Color::srgb(1., 0.85, 0.2),
+        1.,
+        (0., 3.),
+        GameState::Menu,
+    );
+
+    let online = spawn_button(&mut cmd, &font, "В бой", 0., GameState::Gameplay);
+    cmd.entity(online).insert(MatchMode::Online);
+    let practice = spawn_button(&mut cmd, &font, "Тренировка", -1.5, GameState::Gameplay);
+    cmd.entity(practice).insert(MatchMode::Practice);
+    // Кнопка записи появляется, только если есть что смотреть
+    if replay.is_some() {
+        spawn_button(&mut cmd, &font, "Запись матча", -3., GameState::Replay);
+    }
+}
+
+/// Кнопка перехода на другой экран
+#[derive(Component)]
+struct MenuButton(GameState);
+
+fn spawn_button(
+    cmd: &mut Commands,
+    font: &FontAssets,
+    text: &str,
+    y: f32,
+    state: GameState,
+) -> Entity {
+    let [text, _] = spawn_text(
+        cmd,
+        text,
+        font.font.clone(),
+        40.,
+        Color::WHITE,
+        1.,
+        (0., y),
+        GameState::Menu,
+    );
+    cmd.entity(text)
+        .insert((MenuButton(state), UiHitbox(4., 1.)))
+        .observe(on_menu_button);
+    text
+}
+
+fn on_menu_button(
+    trigger: Trigger<OnPress>,
+    buttons: Query<(&MenuButton, Option<&MatchMode>)>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut cmd: Commands,
+) {
+    let Ok((button, mode)) = buttons.get(trigger.entity()) else {
+        return;
+    };
+    if let Some(&mode) = mode {
+        cmd.insert_resource(mode);
+    }
+    next_state.set(button.0);
+}
@@ -2,16 +2,20 @@ use bevy::prelude::*;
 
 mod gameplay;
 mod loading;
+mod menu;
 mod splash;
 mod ui;
 
 pub(super) fn plugin(app: &mut App) {
     app.init_state::<GameState>();
+    app.add_computed_state::<InMatch>();
     app.enable_state_scoped_entities::<GameState>();
+    app.enable_state_scoped_entities::<InMatch>();
 
     app.add_plugins((
         splash::plugin,
         loading::plugin,
+        menu::plugin,
         gameplay::plugin,
         ui::plugin,
     ));
@@ -22,6 +26,19 @@ pub enum GameState {
     #[default]
     Splash,
     Loading,
-    // Menu,
+    Menu,
     Gameplay,
+    // Просмотр записи матча без подключения к серверу
+    Replay,
+}
+
+/// На экране арена с юнитами: идёт бой или показывается запись
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct InMatch;
+impl ComputedStates for InMatch {
+    type SourceStates = GameState;
+
+    fn compute(state: GameState) -> Option<Self> {
+        matches!(state, GameState::Gameplay | GameState::Replay).then_some(InMatch)
+    }
 }
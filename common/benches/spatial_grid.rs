@@ -0,0 +1,93 @@
+//! Сравнение поиска соседей перебором и через SpatialGrid.
+//! Запуск: cargo bench -p common
+
+use std::{hint::black_box, time::Instant};
+
+use bevy::prelude::*;
+use common::{ArenaPos, SpatialGrid};
+
+// Радиус агрессии, огненного шара и взрыва бомбы
+const RADII: [f32; 3] = [5.5, 2.5, 1.5];
+const ROUNDS: u32 = 20;
+// Как на сервере
+const CELL_SIZE: f32 = 2.;
+
+/// Простой генератор, чтобы расстановка не менялась от запуска к запуску
+struct Lcg(u64);
+impl Lcg {
+    fn next(&mut self) -> f32 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.0 >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+fn units(count: u32) -> Vec<(Entity, ArenaPos)> {
+    let mut rng = Lcg(count as u64);
+    (0..count)
+        .map(|i| {
+            let pos = ArenaPos(rng.next() * 18. - 9., rng.next() * 32. - 16.);
+            (Entity::from_raw(i), pos)
+        })
+        .collect()
+}
+
+fn brute_force(units: &[(Entity, ArenaPos)], radius: f32) -> usize {
+    let mut found = 0;
+    for (_, center) in units {
+        for (_, pos) in units {
+            if center.distance(pos) <= radius {
+                found += 1;
+            }
+        }
+    }
+    found
+}
+
+fn grid(grid: &mut SpatialGrid, units: &[(Entity, ArenaPos)], radius: f32) -> usize {
+    // Перестройка входит в замер, как и на сервере каждый тик
+    grid.clear();
+    for &(entity, pos) in units {
+        grid.insert(entity, pos, 0.5);
+    }
+
+    let mut found = 0;
+    for (_, center) in units {
+        for entity in grid.nearby(*center, radius) {
+            // Как Query::get на сервере
+            let (_, pos) = units[entity.index() as usize];
+            if center.distance(&pos) <= radius {
+                found += 1;
+            }
+        }
+    }
+    found
+}
+
+fn measure(mut f: impl FnMut() -> usize) -> (f64, usize) {
+    let mut found = 0;
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        found = black_box(f());
+    }
+    (start.elapsed().as_secs_f64() * 1000. / ROUNDS as f64, found)
+}
+
+fn main() {
+    println!("радиус   юнитов   перебор, мс   сетка, мс");
+    for radius in RADII {
+        for count in [100, 300, 600, 1200] {
+            let units = units(count);
+            let mut spatial = SpatialGrid::new(CELL_SIZE);
+
+            let (brute_ms, brute_found) = measure(|| brute_force(black_box(&units), radius));
+            let (grid_ms, grid_found) =
+                measure(|| grid(&mut spatial, black_box(&units), radius));
+            assert_eq!(brute_found, grid_found, "сетка потеряла соседей");
+
+            println!("{radius:>6}   {count:>6}   {brute_ms:>11.3}   {grid_ms:>9.3}");
+        }
+    }
+}
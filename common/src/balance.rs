@@ -0,0 +1,506 @@
+//! Характеристики юнитов и карт, загружаемые из assets/data/*.ron.
+//! Сервер применяет их при спавне, клиент берёт стоимость карт и время развёртывания
+
+use std::{collections::HashMap, fs, path::Path};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{ArenaPos, Card, PlayerNumber, Projectile, Spell, StatusEffect, Unit};
+
+/// Пути к файлам баланса относительно папки assets
+pub const UNITS_PATH: &str = "data/units.ron";
+pub const CARDS_PATH: &str = "data/cards.ron";
+
+#[derive(Component, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum UnitType {
+    Air,
+    Ground,
+    // Башни и постройки из карт. Не двигаются, притягивают наземных юнитов
+    Building,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub enum AttackType {
+    Melee(u16),              // Урон
+    Ranged(Projectile, u16), // Снаряд и урон от его попадания
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AttackTargetType {
+    Ground,
+    All,
+    // Только постройки, остальных юнитов не замечает
+    Buildings,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AttackStats {
+    pub attack_type: AttackType,
+    pub targets: AttackTargetType,
+    pub cooldown: f32,
+    pub range: f32,
+    // Эффект, который снаряд накладывает на цель, и его длительность в секундах
+    #[serde(default)]
+    pub on_hit: Option<(StatusEffect, f32)>,
+    // Сила отбрасывания от точки попадания, только у снарядов с областью поражения
+    #[serde(default)]
+    pub knockback: f32,
+}
+
+/// Постройка периодически выпускает юнитов
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SpawnerStats {
+    pub unit: Unit,
+    pub interval: f32,
+}
+
+/// Разгон: пройдя без остановки distance клеток, юнит ускоряется до speed.
+/// Первый удар после разгона наносит двойной урон
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChargeStats {
+    pub distance: f32,
+    pub speed: f32,
+}
+
+/// Рывок к цели, которая дальше min_range, но ближе max_range.
+/// Приземление наносит обычный удар ближнего боя
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DashStats {
+    pub min_range: f32,
+    pub max_range: f32,
+    pub speed: f32,
+    pub cooldown: f32,
+}
+
+/// Юнит периодически лечит союзников вокруг себя, кроме построек
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HealStats {
+    pub amount: u16,
+    pub radius: f32,
+    pub interval: f32,
+}
+
+/// Что происходит при гибели юнита, до того как он исчезнет
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum DeathEffect {
+    // Юниты владельца появляются на месте погибшего
+    Spawn { unit: Unit, count: u8 },
+    // Урон по врагам в радиусе, растёт с уровнем
+    Damage { damage: u16, radius: f32 },
+    // Эликсир противнику владельца, то есть тому, кто уничтожил юнита
+    Elixir(u8),
+}
+
+/// Башни арены, у которых есть своё поведение
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum TowerKind {
+    Archer,
+    // Бездействует, пока её не атакуют или не разрушат башню лучников
+    King,
+}
+
+/// Спрайт юнита. Пути указываются относительно папки assets
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum SpriteView {
+    // Анимация с тегами направлений u/d/l/r и атаки ua/da/la/ra
+    Animation(String),
+    // Отдельные анимации для своих и вражеских юнитов
+    SidedAnimation {
+        ally: String,
+        enemy: String,
+    },
+    // Статичный slice, для построек
+    SidedSlice {
+        ally: String,
+        enemy: String,
+        slice: String,
+    },
+}
+impl SpriteView {
+    pub fn path(&self, ally: bool) -> &str {
+        match (self, ally) {
+            (SpriteView::Animation(path), _) => path,
+            (SpriteView::SidedAnimation { ally: path, .. }, true)
+            | (SpriteView::SidedSlice { ally: path, .. }, true) => path,
+            (SpriteView::SidedAnimation { enemy: path, .. }, false)
+            | (SpriteView::SidedSlice { enemy: path, .. }, false) => path,
+        }
+    }
+    pub fn paths(&self) -> Vec<&str> {
+        match self {
+            SpriteView::Animation(path) => vec![path],
+            SpriteView::SidedAnimation { ally, enemy }
+            | SpriteView::SidedSlice { ally, enemy, .. } => vec![ally, enemy],
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SpriteLayer {
+    pub source: SpriteView,
+    pub scale: f32,
+    // Высота спрайта над точкой юнита на арене
+    pub height: f32,
+}
+
+/// Отображение юнита на клиенте, сервер его не использует
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UnitView {
+    pub name: String,
+    pub sprite: SpriteLayer,
+    // Башня, на которой стоит юнит
+    #[serde(default)]
+    pub tower: Option<SpriteLayer>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UnitStats {
+    pub health: u16,
+    pub unit_type: UnitType,
+    pub hitbox: f32,
+    // Чем тяжелее юнит, тем слабее его отбрасывает
+    #[serde(default = "default_mass")]
+    pub mass: f32,
+    // Время в секундах от розыгрыша карты до начала действий юнита
+    #[serde(default)]
+    pub deploy_time: f32,
+    // У построек нет скорости и радиуса агрессии
+    #[serde(default)]
+    pub speed: Option<f32>,
+    #[serde(default)]
+    pub aggro_radius: Option<f32>,
+    #[serde(default)]
+    pub attack: Option<AttackStats>,
+    // Время жизни построек из карт в секундах
+    #[serde(default)]
+    pub lifetime: Option<u16>,
+    #[serde(default)]
+    pub spawner: Option<SpawnerStats>,
+    #[serde(default)]
+    pub tower: Option<TowerKind>,
+    // Способности передвижения, только для юнитов ближнего боя
+    #[serde(default)]
+    pub charge: Option<ChargeStats>,
+    #[serde(default)]
+    pub dash: Option<DashStats>,
+    #[serde(default)]
+    pub heal: Option<HealStats>,
+    // Щит поглощает урон раньше здоровья и не лечится
+    #[serde(default)]
+    pub shield: Option<u16>,
+    #[serde(default)]
+    pub death: Vec<DeathEffect>,
+    pub view: UnitView,
+}
+
+fn default_mass() -> f32 {
+    1.
+}
+
+/// Заклинание летит от башни короля к точке розыгрыша и действует на всех в радиусе.
+/// Урон растёт с уровнем карты
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SpellStats {
+    pub radius: f32,
+    // Скорость полёта
+    pub speed: f32,
+    #[serde(default)]
+    pub damage: u16,
+    // По башням заклинания наносят уменьшенный урон
+    #[serde(default)]
+    pub tower_damage: u16,
+    // Эффект и его длительность в секундах
+    #[serde(default)]
+    pub effect: Option<(StatusEffect, f32)>,
+    // Сила отбрасывания от центра заклинания
+    #[serde(default)]
+    pub knockback: f32,
+    // Действует на юнитов заклинателя, а не противника
+    #[serde(default)]
+    pub allies: bool,
+    pub view: SpellView,
+}
+
+/// Отображение заклинания на клиенте, сервер его не использует
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SpellView {
+    pub name: String,
+    pub sprite: SpellSprite,
+    // Оттенок спрайта в sRGB
+    #[serde(default)]
+    pub tint: Option<(f32, f32, f32)>,
+    // Наибольшая высота дуги над прямой траекторией
+    pub peak: f32,
+}
+
+/// Спрайт заклинания. Пути указываются относительно папки assets
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum SpellSprite {
+    // Анимация целиком или только один её тег
+    Animation {
+        path: String,
+        #[serde(default)]
+        tag: Option<String>,
+    },
+    // Копии slice со смещениями в пикселях спрайта, как у залпа стрел
+    Volley {
+        path: String,
+        slice: String,
+        offsets: Vec<(f32, f32)>,
+    },
+}
+impl SpellSprite {
+    pub fn path(&self) -> &str {
+        match self {
+            SpellSprite::Animation { path, .. } | SpellSprite::Volley { path, .. } => path,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CardStats {
+    pub cost: u8,
+    // Юниты и их смещения от точки розыгрыша. У заклинаний пусто
+    #[serde(default)]
+    pub formation: Vec<(Unit, ArenaPos)>,
+    // Только у заклинаний
+    #[serde(default)]
+    pub spell: Option<SpellStats>,
+}
+impl CardStats {
+    /// Расстановка задаётся для первого игрока. У второго она отражается,
+    /// чтобы передние юниты так же стояли ближе к противнику
+    pub fn formation_for(
+        &self,
+        player_num: PlayerNumber,
+    ) -> impl Iterator<Item = (Unit, ArenaPos)> + '_ {
+        self.formation
+            .iter()
+            .map(move |&(unit, ArenaPos(x, y))| match player_num {
+                PlayerNumber::One => (unit, ArenaPos(x, y)),
+                PlayerNumber::Two => (unit, ArenaPos(-x, -y)),
+            })
+    }
+}
+
+#[derive(Resource, Debug, Serialize, Deserialize, Clone, Deref)]
+pub struct UnitsData(pub HashMap<Unit, UnitStats>);
+impl UnitsData {
+    pub fn stats(&self, unit: Unit) -> &UnitStats {
+        self.0
+            .get(&unit)
+            .unwrap_or_else(|| panic!("Нет характеристик юнита {unit:?} в {UNITS_PATH}"))
+    }
+
+    /// Проверка значений, которые ron пропустит, но с которыми игра сломается
+    pub fn validate(&self) -> Result<(), String> {
+        // stats() паникует на отсутствующем юните, поэтому нужны все
+        if let Some(unit) = Unit::ALL.iter().find(|unit| !self.0.contains_key(unit)) {
+            return Err(format!("{unit:?}: нет характеристик юнита"));
+        }
+        for (unit, stats) in &self.0 {
+            let positive = [
+                ("health", stats.health as f32),
+                ("hitbox", stats.hitbox),
+                ("mass", stats.mass),
+                ("speed", stats.speed.unwrap_or(1.)),
+                ("aggro_radius", stats.aggro_radius.unwrap_or(1.)),
+                (
+                    "attack.cooldown",
+                    stats.attack.as_ref().map_or(1., |a| a.cooldown),
+                ),
+                (
+                    "attack.range",
+                    stats.attack.as_ref().map_or(1., |a| a.range),
+                ),
+                (
+                    "attack.on_hit",
+                    stats
+                        .attack
+                        .as_ref()
+                        .and_then(|a| a.on_hit)
+                        .map_or(1., |(_, duration)| duration),
+                ),
+                ("lifetime", stats.lifetime.unwrap_or(1) as f32),
+                (
+                    "spawner.interval",
+                    stats.spawner.as_ref().map_or(1., |s| s.interval),
+                ),
+                (
+                    "charge.distance",
+                    stats.charge.as_ref().map_or(1., |c| c.distance),
+                ),
+                (
+                    "charge.speed",
+                    stats.charge.as_ref().map_or(1., |c| c.speed),
+                ),
+                ("dash.speed", stats.dash.as_ref().map_or(1., |d| d.speed)),
+                (
+                    "dash.cooldown",
+                    stats.dash.as_ref().map_or(1., |d| d.cooldown),
+                ),
+                ("heal.radius", stats.heal.as_ref().map_or(1., |h| h.radius)),
+                (
+                    "heal.interval",
+                    stats.heal.as_ref().map_or(1., |h| h.interval),
+                ),
+                ("shield", stats.shield.unwrap_or(1) as f32),
+            ];
+            for (field, value) in positive {
+                if value <= 0. {
+                    return Err(format!("{unit:?}: {field} должно быть больше нуля"));
+                }
+            }
+            let melee = matches!(
+                stats.attack.as_ref().map(|a| a.attack_type),
+                Some(AttackType::Melee(_))
+            );
+            if (stats.charge.is_some() || stats.dash.is_some()) && !melee {
+                return Err(format!("{unit:?}: разгон и рывок только для ближнего боя"));
+            }
+            if stats.attack.as_ref().is_some_and(|a| a.on_hit.is_some()) && melee {
+                return Err(format!("{unit:?}: on_hit только для снарядов"));
+            }
+            if let Some(attack) = &stats.attack {
+                let splash = matches!(
+                    attack.attack_type,
+                    AttackType::Ranged(Projectile::Fireball | Projectile::Bomb, _)
+                );
+                if attack.knockback < 0. || attack.knockback > 0. && !splash {
+                    return Err(format!(
+                        "{unit:?}: knockback только для снарядов с областью поражения"
+                    ));
+                }
+            }
+            if let Some(dash) = &stats.dash {
+                if dash.min_range < 0. || dash.min_range >= dash.max_range {
+                    return Err(format!(
+                        "{unit:?}: dash.min_range должно быть меньше max_range"
+                    ));
+                }
+            }
+            if stats.deploy_time < 0. {
+                return Err(format!("{unit:?}: deploy_time не может быть отрицательным"));
+            }
+            if let Some(spawner) = &stats.spawner {
+                if !self.0.contains_key(&spawner.unit) {
+                    return Err(format!(
+                        "{unit:?}: нет характеристик юнита {:?}",
+                        spawner.unit
+                    ));
+                }
+            }
+            for effect in &stats.death {
+                match effect {
+                    DeathEffect::Spawn {
+                        unit: spawned,
+                        count,
+                    } => {
+                        if !self.0.contains_key(spawned) {
+                            return Err(format!(
+                                "{unit:?}: нет характеристик юнита {spawned:?}"
+                            ));
+                        }
+                        if *count == 0 {
+                            return Err(format!(
+                                "{unit:?}: death.count должно быть больше нуля"
+                            ));
+                        }
+                    }
+                    DeathEffect::Damage { radius, .. } if *radius <= 0. => {
+                        return Err(format!("{unit:?}: death.radius должно быть больше нуля"));
+                    }
+                    DeathEffect::Elixir(amount) if *amount > 10 => {
+                        return Err(format!("{unit:?}: death.elixir больше 10 эликсира"));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Resource, Debug, Serialize, Deserialize, Clone, Deref)]
+pub struct CardsData(pub HashMap<Card, CardStats>);
+impl CardsData {
+    pub fn stats(&self, card: Card) -> &CardStats {
+        self.0
+            .get(&card)
+            .unwrap_or_else(|| panic!("Нет характеристик карты {card:?} в {CARDS_PATH}"))
+    }
+
+    /// У карт заклинаний характеристики есть всегда, это проверяет validate
+    pub fn spell(&self, spell: Spell) -> &SpellStats {
+        let card = Card::from(spell);
+        self.stats(card)
+            .spell
+            .as_ref()
+            .unwrap_or_else(|| panic!("Нет характеристик заклинания {card:?} в {CARDS_PATH}"))
+    }
+
+    pub fn validate(&self, units: &UnitsData) -> Result<(), String> {
+        if let Some(card) = Card::ALL.iter().find(|card| !self.0.contains_key(card)) {
+            return Err(format!("{card:?}: нет характеристик карты"));
+        }
+        for (card, stats) in &self.0 {
+            if stats.cost > 10 {
+                return Err(format!("{card:?}: стоимость больше 10 эликсира"));
+            }
+            match (card.spell(), &stats.spell) {
+                (Some(_), _) if !stats.formation.is_empty() => {
+                    return Err(format!("{card:?}: у заклинания не может быть юнитов"));
+                }
+                (Some(_), None) => {
+                    return Err(format!("{card:?}: нет характеристик заклинания"));
+                }
+                (Some(_), Some(spell)) => {
+                    let effect = spell.effect.map_or(1., |(_, duration)| duration);
+                    if spell.radius <= 0. || spell.speed <= 0. || effect <= 0. {
+                        return Err(format!(
+                            "{card:?}: радиус, скорость и длительность эффекта должны быть больше нуля"
+                        ));
+                    }
+                }
+                (None, Some(_)) => {
+                    return Err(format!(
+                        "{card:?}: характеристики заклинания у карты юнитов"
+                    ));
+                }
+                (None, None) if stats.formation.is_empty() => {
+                    return Err(format!("{card:?}: у карты нет юнитов"));
+                }
+                (None, None) => {}
+            }
+            for (unit, _) in &stats.formation {
+                if !units.contains_key(unit) {
+                    return Err(format!("{card:?}: нет характеристик юнита {unit:?}"));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn parse_ron<T: for<'de> Deserialize<'de>>(
+    bytes: &[u8],
+) -> Result<T, ron::error::SpannedError> {
+    ron::de::from_bytes(bytes)
+}
+
+/// Читает RON-файл с диска. В ошибке есть путь к файлу
+pub fn load_ron<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T, String> {
+    let bytes =
+        fs::read(path).map_err(|e| format!("Не удалось прочитать {}: {e}", path.display()))?;
+    parse_ron(&bytes).map_err(|e| format!("Ошибка в {}: {e}", path.display()))
+}
+
+/// Читает и проверяет units.ron и cards.ron из папки assets
+pub fn load_balance(dir: &Path) -> Result<(UnitsData, CardsData), String> {
+    let units: UnitsData = load_ron(&dir.join(UNITS_PATH))?;
+    let cards: CardsData = load_ron(&dir.join(CARDS_PATH))?;
+    units.validate()?;
+    cards.validate(&units)?;
+    Ok((units, cards))
+}
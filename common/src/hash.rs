@@ -0,0 +1,101 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{ArenaPos, CardsData, Health, UnitState, UnitsData};
+
+/// FNV-1a. Результат не зависит от платформы и версии компилятора,
+/// поэтому его можно сохранять в файлы и сравнивать между машинами
+pub struct Fnv(u64);
+impl Fnv {
+    pub fn new() -> Self {
+        Self(0xCBF2_9CE4_8422_2325)
+    }
+
+    pub fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01B3);
+        }
+    }
+
+    pub fn finish(&self) -> u64 {
+        self.0
+    }
+}
+impl Default for Fnv {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Хеш таблиц баланса. Записи сортируются, чтобы порядок HashMap не влиял на результат
+pub fn balance_hash(units: &UnitsData, cards: &CardsData) -> u64 {
+    let mut entries: Vec<Vec<u8>> = units.iter().map(serialize).collect();
+    entries.sort_unstable();
+    let mut card_entries: Vec<Vec<u8>> = cards.iter().map(serialize).collect();
+    card_entries.sort_unstable();
+
+    let mut fnv = Fnv::new();
+    for entry in entries.iter().chain(&card_entries) {
+        fnv.write(entry);
+    }
+    fnv.finish()
+}
+
+fn serialize(entry: impl Serialize) -> Vec<u8> {
+    bincode::serialize(&entry).unwrap()
+}
+
+/// Через сколько тиков сервер добавляет контрольную сумму в SyncEntities
+pub const CHECKSUM_INTERVAL: u64 = 32;
+/// Положения округляются до 1/16 клетки, чтобы погрешность float
+/// на разных машинах не давала ложных расхождений
+const CHECKSUM_PRECISION: f32 = 16.;
+
+/// Часть состояния юнита, которая входит в контрольную сумму
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ChecksumEntry {
+    // Entity сервера
+    pub entity: Entity,
+    pub pos: (i32, i32),
+    pub state: UnitState,
+    pub health: u16,
+}
+impl ChecksumEntry {
+    pub fn new(entity: Entity, pos: ArenaPos, state: UnitState, health: Health) -> Self {
+        let quantize = |v: f32| (v * CHECKSUM_PRECISION).round() as i32;
+        Self {
+            entity,
+            pos: (quantize(pos.0), quantize(pos.1)),
+            state,
+            health: health.0,
+        }
+    }
+}
+
+/// Контрольная сумма юнитов на конце тика. Дешевле полного снимка,
+/// по ней клиент с локальной симуляцией замечает рассинхронизацию
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Checksum {
+    pub tick: u64,
+    pub hash: u64,
+}
+impl Checksum {
+    /// Записи сортируются по Entity, порядок обхода юнитов не важен
+    pub fn new(tick: u64, entries: &mut [ChecksumEntry]) -> Self {
+        entries.sort_unstable_by_key(|entry| entry.entity);
+        let mut fnv = Fnv::new();
+        fnv.write(&tick.to_le_bytes());
+        for entry in entries.iter() {
+            fnv.write(&entry.entity.to_bits().to_le_bytes());
+            fnv.write(&entry.pos.0.to_le_bytes());
+            fnv.write(&entry.pos.1.to_le_bytes());
+            fnv.write(&[entry.state as u8]);
+            fnv.write(&entry.health.to_le_bytes());
+        }
+        Self {
+            tick,
+            hash: fnv.finish(),
+        }
+    }
+}
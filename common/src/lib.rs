@@ -7,10 +7,30 @@ use bevy::{math::vec2, prelude::*};
 use bevy_quinnet::shared::channels::{ChannelId, ChannelType, ChannelsConfiguration};
 use serde::{Deserialize, Serialize};
 
+mod balance;
+mod hash;
+mod replay;
+mod spatial;
+pub use balance::*;
+pub use hash::*;
+pub use replay::*;
+pub use spatial::*;
+
 //pub const SERVER_HOST: Ipv4Addr = Ipv4Addr::new(178, 71, 57, 127);
 pub const SERVER_HOST: Ipv4Addr = Ipv4Addr::LOCALHOST;
 pub const LOCAL_BIND_IP: Ipv4Addr = Ipv4Addr::UNSPECIFIED;
 pub const SERVER_PORT: u16 = 50505;
+/// Увеличивается при несовместимых изменениях ClientMessage и ServerMessage
+pub const PROTOCOL_VERSION: u16 = 1;
+
+/// Границы арены в клетках от её центра. Река проходит по y = 0
+pub const ARENA_HALF_WIDTH: f32 = 9.;
+pub const ARENA_HALF_HEIGHT: f32 = 16.;
+pub const RIVER_HALF_WIDTH: f32 = 1.;
+
+/// Эликсир копится по единице за интервал в секундах, но не больше MAX_ELIXIR
+pub const ELIXIR_INTERVAL: f32 = 1.5;
+pub const MAX_ELIXIR: u8 = 10;
 
 #[derive(
     Debug,
@@ -64,7 +84,9 @@ impl ArenaPos {
     }
 }
 
-#[derive(Debug, Component, Serialize, Deserialize, Clone, Copy, Reflect)]
+#[derive(
+    Debug, Component, Serialize, Deserialize, Clone, Copy, Reflect, PartialEq, Eq, Hash,
+)]
 #[reflect(Component)]
 pub enum Card {
     Rus,
@@ -75,9 +97,48 @@ pub enum Card {
     BatHorde,
     Bomber,
     Giant,
+    Fireball,
+    Arrows,
+    Cannon,
+    Hut,
+    Zap,
+    Freeze,
+    Rage,
 }
+impl Card {
+    /// Все карты. По нему проверяется, что у каждой есть характеристики
+    pub const ALL: [Card; 15] = [
+        Card::Rus,
+        Card::Musketeer,
+        Card::ThreeMusketeers,
+        Card::Priest,
+        Card::Bats,
+        Card::BatHorde,
+        Card::Bomber,
+        Card::Giant,
+        Card::Fireball,
+        Card::Arrows,
+        Card::Cannon,
+        Card::Hut,
+        Card::Zap,
+        Card::Freeze,
+        Card::Rage,
+    ];
 
-#[derive(Debug, Component, Serialize, Deserialize, Clone, Copy)]
+    /// Заклинания разыгрываются в любой точке арены, а не спавнят юнитов
+    pub fn spell(&self) -> Option<Spell> {
+        match self {
+            Card::Fireball => Some(Spell::Fireball),
+            Card::Arrows => Some(Spell::Arrows),
+            Card::Zap => Some(Spell::Zap),
+            Card::Freeze => Some(Spell::Freeze),
+            Card::Rage => Some(Spell::Rage),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Component, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Unit {
     ArcherTower,
     KingTower,
@@ -87,6 +148,23 @@ pub enum Unit {
     Priest,
     Bomber,
     Giant,
+    Cannon,
+    Hut,
+}
+impl Unit {
+    /// Все юниты. По нему проверяется, что у каждого есть характеристики
+    pub const ALL: [Unit; 10] = [
+        Unit::ArcherTower,
+        Unit::KingTower,
+        Unit::Rus,
+        Unit::Musketeer,
+        Unit::Bat,
+        Unit::Priest,
+        Unit::Bomber,
+        Unit::Giant,
+        Unit::Cannon,
+        Unit::Hut,
+    ];
 }
 
 #[derive(Debug, Component, Serialize, Deserialize, Clone, Copy)]
@@ -96,7 +174,48 @@ pub enum Projectile {
     Bomb,
 }
 
-#[derive(Component, Reflect, Serialize, Deserialize, Clone, Copy)]
+#[derive(Debug, Component, Serialize, Deserialize, Clone, Copy)]
+pub enum Spell {
+    Fireball,
+    Arrows,
+    Zap,
+    Freeze,
+    Rage,
+}
+impl From<Spell> for Card {
+    fn from(spell: Spell) -> Self {
+        match spell {
+            Spell::Fireball => Card::Fireball,
+            Spell::Arrows => Card::Arrows,
+            Spell::Zap => Card::Zap,
+            Spell::Freeze => Card::Freeze,
+            Spell::Rage => Card::Rage,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum StatusEffect {
+    Stun,
+    Slow,
+    Rage,
+    Freeze,
+}
+
+/// Набор действующих на юнита эффектов, отправляемый клиенту
+#[derive(Component, Reflect, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[reflect(Component)]
+pub struct ActiveEffects(pub u8);
+impl ActiveEffects {
+    pub fn insert(&mut self, effect: StatusEffect) {
+        self.0 |= 1 << effect as u8;
+    }
+    pub fn contains(&self, effect: StatusEffect) -> bool {
+        self.0 & 1 << effect as u8 != 0
+    }
+}
+
+#[derive(Component, Reflect, Serialize, Deserialize, Clone, Copy, Debug)]
 #[reflect(Component)]
 pub struct Health(pub u16, pub u16); // Текущее и максимальное здоровье
 impl Health {
@@ -111,6 +230,42 @@ impl Default for Health {
     }
 }
 
+/// Щит юнита, теряется раньше здоровья
+#[derive(
+    Component, Reflect, Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq,
+)]
+#[reflect(Component)]
+pub struct Shield(pub u16, pub u16); // Текущий и максимальный щит
+impl Shield {
+    pub fn new(amount: u16) -> Self {
+        Shield(amount, amount)
+    }
+}
+
+/// Уровень карты, из которой выставлен юнит или разыграно заклинание
+#[derive(Component, Reflect, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component)]
+pub struct Level(pub u8);
+impl Level {
+    pub const MAX: u8 = 5;
+    // Множитель здоровья и урона для каждого уровня, начиная с первого
+    const MULTIPLIERS: [f32; Self::MAX as usize] = [1., 1.1, 1.21, 1.33, 1.46];
+
+    /// Уровни вне таблицы приводятся к ближайшему допустимому
+    pub fn new(level: u8) -> Self {
+        Self(level.clamp(1, Self::MAX))
+    }
+    pub fn scale(&self, value: u16) -> u16 {
+        let multiplier = Self::MULTIPLIERS[(self.0.clamp(1, Self::MAX) - 1) as usize];
+        (value as f32 * multiplier).round() as u16
+    }
+}
+impl Default for Level {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
 #[derive(Component, Debug, Serialize, Deserialize, Clone, Copy, Reflect, Default)]
 #[reflect(Component)]
 pub enum Direction {
@@ -132,17 +287,24 @@ impl Direction {
     }
 }
 
-#[derive(Component, Debug, Serialize, Deserialize, Clone, Copy, Reflect, Default)]
+#[derive(
+    Component, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Reflect, Default,
+)]
 #[reflect(Component)]
 pub enum UnitState {
     #[default]
-    Idle, // Для построек, а также отправляется клиенту для юнитов в стане
+    Idle, // Для построек
     Moving, // Для всего остального
     Attacking,
+    Deploying, // Юнит только что выставлен и ещё не может действовать
+    Charging,  // Разогнался, первый удар будет двойным
+    Dashing,   // Рывок к цели
 }
 
 #[derive(Serialize, Deserialize)]
 pub enum ClientMessage {
+    // Колода игрока с уровнями карт, отправляется в начале матча
+    SetDeck(Vec<(Card, Level)>),
     PlayCard { card: Card, placement: ArenaPos },
 }
 
@@ -164,6 +326,14 @@ pub enum PlayerNumber {
     One, // Игрок "снизу"
     Two, // Игрок "сверху"
 }
+impl PlayerNumber {
+    pub fn opponent(self) -> Self {
+        match self {
+            PlayerNumber::One => PlayerNumber::Two,
+            PlayerNumber::Two => PlayerNumber::One,
+        }
+    }
+}
 
 #[derive(Serialize, Deserialize)]
 pub enum ServerMessage {
@@ -173,6 +343,7 @@ pub enum ServerMessage {
         unit: Unit,
         pos: ArenaPos,
         owner: PlayerNumber,
+        level: Level,
     },
     SpawnProjectile {
         server_entity: Entity,
@@ -181,10 +352,43 @@ pub enum ServerMessage {
         receiver: Entity,
         pos: ArenaPos,
     },
+    SpawnSpell {
+        server_entity: Entity,
+        spell: Spell,
+        pos: ArenaPos,
+        target: ArenaPos,
+    },
     Despawn(Entity),
+    // Юнит погиб, клиент проигрывает анимацию смерти вместо мгновенного исчезновения
+    UnitDied(Entity),
+    // Эликсир за уничтоженного юнита
+    AddElixir(u8),
+    // Розыгрыш не принят. Клиент возвращает карту в руку
+    // и берёт эликсир сервера вместо своего
+    PlayRejected {
+        card: Card,
+        elixir: f32,
+    },
+    // Башня короля перестала бездействовать
+    ActivateKingTower(Entity),
+    // Файлы баланса изменились на dev-сервере
+    UpdateBalance {
+        units: UnitsData,
+        cards: CardsData,
+    },
     SyncEntities {
-        units: Vec<(Entity, ArenaPos, Direction, UnitState, Health)>,
+        units: Vec<(
+            Entity,
+            ArenaPos,
+            Direction,
+            UnitState,
+            Health,
+            Shield,
+            ActiveEffects,
+        )>,
         projectiles: Vec<(Entity, ArenaPos)>,
+        // Раз в CHECKSUM_INTERVAL тиков
+        checksum: Option<Checksum>,
     },
 }
 
@@ -0,0 +1,79 @@
+//! Формат файла записи матча: 4 байта сигнатуры, версия формата (u16, LE)
+//! и bincode-тело Replay
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ArenaPos, Card, Level, PlayerNumber};
+
+const REPLAY_MAGIC: &[u8; 4] = b"BTRP";
+/// Увеличивается при любом изменении Replay
+pub const REPLAY_VERSION: u16 = 1;
+pub const REPLAY_EXTENSION: &str = "replay";
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ReplayHeader {
+    pub protocol: u16,
+    // balance_hash таблиц, на которых шёл матч
+    pub balance_hash: u64,
+    pub seed: u64,
+    // Колоды игроков One и Two
+    pub decks: [Vec<(Card, Level)>; 2],
+}
+
+/// Принятый сервером розыгрыш карты
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct ReplayInput {
+    // Тик от начала матча
+    pub tick: u64,
+    pub player: PlayerNumber,
+    pub card: Card,
+    pub placement: ArenaPos,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Replay {
+    pub header: ReplayHeader,
+    // В порядке принятия сервером
+    pub inputs: Vec<ReplayInput>,
+    // Тик, на котором матч закончился
+    pub length: u64,
+}
+impl Replay {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = REPLAY_MAGIC.to_vec();
+        bytes.extend(REPLAY_VERSION.to_le_bytes());
+        bytes.extend(bincode::serialize(self).unwrap());
+        bytes
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, String> {
+        let Some((magic, rest)) = bytes.split_first_chunk::<4>() else {
+            return Err("Файл слишком короткий".into());
+        };
+        if magic != REPLAY_MAGIC {
+            return Err("Это не файл записи матча".into());
+        }
+        let Some((version, body)) = rest.split_first_chunk::<2>() else {
+            return Err("Файл слишком короткий".into());
+        };
+        let version = u16::from_le_bytes(*version);
+        if version != REPLAY_VERSION {
+            return Err(format!(
+                "Запись версии {version}, поддерживается только {REPLAY_VERSION}"
+            ));
+        }
+        bincode::deserialize(body).map_err(|e| format!("Повреждённая запись: {e}"))
+    }
+
+    pub fn deck(&self, player: PlayerNumber) -> &[(Card, Level)] {
+        &self.header.decks[player as usize]
+    }
+
+    /// Уровень карты в колоде игрока, по умолчанию первый
+    pub fn level(&self, player: PlayerNumber, card: Card) -> Level {
+        self.deck(player)
+            .iter()
+            .find(|(c, _)| *c == card)
+            .map_or(Level::default(), |(_, level)| *level)
+    }
+}
@@ -0,0 +1,63 @@
+//! Равномерная сетка по ArenaPos для поиска юнитов рядом с точкой
+
+use bevy::prelude::*;
+
+use crate::{ArenaPos, ARENA_HALF_HEIGHT, ARENA_HALF_WIDTH};
+
+/// Сущности раскладываются по квадратным ячейкам, покрывающим арену. Поиск
+/// просматривает только ячейки, пересекающие нужный радиус, вместо перебора всех юнитов.
+/// Всё, что оказалось за краем арены, попадает в крайние ячейки
+pub struct SpatialGrid {
+    cell_size: f32,
+    columns: i32,
+    rows: i32,
+    cells: Vec<Vec<(Entity, ArenaPos)>>,
+    // Наибольший хитбокс среди добавленных, расширяет область поиска
+    max_extent: f32,
+}
+impl SpatialGrid {
+    pub fn new(cell_size: f32) -> Self {
+        let columns = (2. * ARENA_HALF_WIDTH / cell_size).ceil() as i32;
+        let rows = (2. * ARENA_HALF_HEIGHT / cell_size).ceil() as i32;
+        Self {
+            cell_size,
+            columns,
+            rows,
+            cells: vec![Vec::new(); (columns * rows) as usize],
+            max_extent: 0.,
+        }
+    }
+
+    /// Ячейки сохраняют выделенную память, чтобы не аллоцировать каждый тик
+    pub fn clear(&mut self) {
+        for cell in &mut self.cells {
+            cell.clear();
+        }
+        self.max_extent = 0.;
+    }
+
+    pub fn insert(&mut self, entity: Entity, pos: ArenaPos, extent: f32) {
+        self.max_extent = self.max_extent.max(extent);
+        let (x, y) = self.cell(pos);
+        self.cells[(y * self.columns + x) as usize].push((entity, pos));
+    }
+
+    /// Все сущности, которые могут оказаться в радиусе от центра с учётом
+    /// своего хитбокса. Точную проверку расстояния делает вызывающий
+    pub fn nearby(&self, center: ArenaPos, radius: f32) -> impl Iterator<Item = Entity> + '_ {
+        let reach = radius + self.max_extent;
+        let (min_x, min_y) = self.cell(ArenaPos(center.0 - reach, center.1 - reach));
+        let (max_x, max_y) = self.cell(ArenaPos(center.0 + reach, center.1 + reach));
+
+        (min_y..=max_y)
+            .flat_map(move |y| (min_x..=max_x).map(move |x| (y * self.columns + x) as usize))
+            .flat_map(|cell| &self.cells[cell])
+            .map(|&(entity, _)| entity)
+    }
+
+    fn cell(&self, pos: ArenaPos) -> (i32, i32) {
+        let x = ((pos.0 + ARENA_HALF_WIDTH) / self.cell_size).floor() as i32;
+        let y = ((pos.1 + ARENA_HALF_HEIGHT) / self.cell_size).floor() as i32;
+        (x.clamp(0, self.columns - 1), y.clamp(0, self.rows - 1))
+    }
+}
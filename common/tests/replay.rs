@@ -0,0 +1,81 @@
+//! Файл записи должен читаться обратно без потерь, а чужой, устаревший
+//! или обрезанный файл должен давать ошибку, а не панику
+
+use common::{
+    ArenaPos, Card, Level, PlayerNumber, Replay, ReplayHeader, ReplayInput, PROTOCOL_VERSION,
+    REPLAY_VERSION,
+};
+
+fn replay() -> Replay {
+    Replay {
+        header: ReplayHeader {
+            protocol: PROTOCOL_VERSION,
+            balance_hash: 0x0123_4567_89ab_cdef,
+            seed: 42,
+            decks: [
+                vec![(Card::Giant, Level(2)), (Card::Fireball, Level(1))],
+                vec![(Card::Bats, Level(3))],
+            ],
+        },
+        inputs: vec![
+            ReplayInput {
+                tick: 30,
+                player: PlayerNumber::One,
+                card: Card::Giant,
+                placement: ArenaPos(-5.5, -4.5),
+            },
+            ReplayInput {
+                tick: 95,
+                player: PlayerNumber::Two,
+                card: Card::Bats,
+                placement: ArenaPos(3.5, 6.),
+            },
+        ],
+        length: 3600,
+    }
+}
+
+#[test]
+fn round_trip() {
+    let bytes = replay().encode();
+    let decoded = Replay::decode(&bytes).unwrap();
+
+    assert_eq!(decoded.header.protocol, PROTOCOL_VERSION);
+    assert_eq!(decoded.header.balance_hash, 0x0123_4567_89ab_cdef);
+    assert_eq!(decoded.header.seed, 42);
+    assert_eq!(decoded.level(PlayerNumber::One, Card::Giant), Level(2));
+    assert_eq!(decoded.level(PlayerNumber::Two, Card::Bats), Level(3));
+    assert_eq!(decoded.inputs.len(), 2);
+    assert_eq!(decoded.inputs[1].tick, 95);
+    assert_eq!(decoded.inputs[1].card, Card::Bats);
+    assert_eq!(decoded.length, 3600);
+    // Всё остальное сверяется побайтно
+    assert_eq!(decoded.encode(), bytes);
+}
+
+#[test]
+fn rejects_bad_magic() {
+    let mut bytes = replay().encode();
+    bytes[..4].copy_from_slice(b"RIFF");
+    assert!(Replay::decode(&bytes).is_err());
+}
+
+#[test]
+fn rejects_other_version() {
+    let mut bytes = replay().encode();
+    bytes[4..6].copy_from_slice(&(REPLAY_VERSION + 1).to_le_bytes());
+    let error = Replay::decode(&bytes).unwrap_err();
+    assert!(error.contains(&(REPLAY_VERSION + 1).to_string()), "{error}");
+}
+
+#[test]
+fn rejects_truncated() {
+    let bytes = replay().encode();
+    // Пустой файл, обрезанные сигнатура, версия и тело
+    for len in [0, 3, 5, 6, bytes.len() / 2, bytes.len() - 1] {
+        assert!(
+            Replay::decode(&bytes[..len]).is_err(),
+            "принят файл из {len} байт"
+        );
+    }
+}
@@ -0,0 +1,176 @@
+//! Матчи бот против бота без графики и сети, быстрее реального времени.
+//!
+//! cargo run --release -p selfplay -- [--matches 1000] [--seed 0] [--threads N] [--max-time 300]
+//!     [--deck1 Giant,Musketeer,...] [--bot1 hard] [--deck2 ...] [--bot2 ...]
+//!     [--format json|csv]
+//!
+//! Матч с номером i идёт с seed + i, а участники каждый второй матч меняются
+//! сторонами, поэтому результат не зависит от числа потоков
+
+use std::{
+    env,
+    path::PathBuf,
+    process::ExitCode,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use common::{load_balance, parse_ron, Card, CardsData, UnitsData};
+use simulation::{Difficulty, BOT_DECK, TICK};
+
+use crate::{
+    report::Report,
+    run::{play_match, Contestant, MatchResult},
+};
+
+mod report;
+mod run;
+
+fn main() -> ExitCode {
+    let result = Args::parse(env::args().skip(1)).and_then(|args| {
+        let (units, cards) = load_balance(&assets_dir())?;
+        let results = run_all(&args, &units, &cards);
+        Report::new(&args.contestants, &results, &cards).write(args.format)
+    });
+    match result {
+        Ok(output) => {
+            println!("{output}");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Format {
+    Json,
+    Csv,
+}
+
+struct Args {
+    matches: usize,
+    seed: u64,
+    threads: usize,
+    // Дальше матч решается по числу разрушенных башен, при равенстве ничья
+    max_ticks: u64,
+    contestants: [Contestant; 2],
+    format: Format,
+}
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut parsed = Self {
+            matches: 100,
+            seed: 0,
+            threads: thread::available_parallelism().map_or(1, |n| n.get()),
+            max_ticks: seconds_to_ticks(300.),
+            contestants: [Contestant::default(), Contestant::default()],
+            format: Format::Json,
+        };
+        while let Some(flag) = args.next() {
+            let value = args
+                .next()
+                .ok_or_else(|| format!("Нет значения для {flag}"))?;
+            let number = || format!("{flag}: ожидалось число, получено {value}");
+            match flag.as_str() {
+                "--matches" => parsed.matches = value.parse().map_err(|_| number())?,
+                "--seed" => parsed.seed = value.parse().map_err(|_| number())?,
+                "--threads" => parsed.threads = value.parse().map_err(|_| number())?,
+                "--max-time" => {
+                    parsed.max_ticks = seconds_to_ticks(value.parse().map_err(|_| number())?)
+                }
+                "--deck1" => parsed.contestants[0].deck = parse_deck(&value)?,
+                "--deck2" => parsed.contestants[1].deck = parse_deck(&value)?,
+                "--bot1" => parsed.contestants[0].difficulty = value.parse()?,
+                "--bot2" => parsed.contestants[1].difficulty = value.parse()?,
+                "--format" => {
+                    parsed.format = match value.as_str() {
+                        "json" => Format::Json,
+                        "csv" => Format::Csv,
+                        _ => return Err(format!("Неизвестный формат {value}")),
+                    }
+                }
+                _ => return Err(format!("Неизвестный аргумент {flag}")),
+            }
+        }
+        parsed.threads = parsed.threads.max(1);
+        Ok(parsed)
+    }
+}
+impl Default for Contestant {
+    fn default() -> Self {
+        Self {
+            deck: BOT_DECK.to_vec(),
+            difficulty: Difficulty::default(),
+        }
+    }
+}
+
+fn seconds_to_ticks(secs: f32) -> u64 {
+    (secs / TICK.as_secs_f32()) as u64
+}
+
+fn seconds_from_ticks(ticks: u64) -> f32 {
+    ticks as f32 * TICK.as_secs_f32()
+}
+
+/// Карты через запятую, названия как в cards.ron
+fn parse_deck(value: &str) -> Result<Vec<Card>, String> {
+    let deck = value
+        .split(',')
+        .map(|name| {
+            parse_ron::<Card>(name.trim().as_bytes()).map_err(|_| format!("Нет карты {name}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if deck.is_empty() {
+        return Err("Пустая колода".to_string());
+    }
+    Ok(deck)
+}
+
+/// Папку assets можно переопределить через BOYAR_ASSETS, как у сервера.
+/// По умолчанию assets в рабочей папке
+fn assets_dir() -> PathBuf {
+    env::var_os("BOYAR_ASSETS")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("assets"))
+}
+
+/// Потоки разбирают матчи по очереди, результаты собираются по номеру матча
+fn run_all(args: &Args, units: &UnitsData, cards: &CardsData) -> Vec<MatchResult> {
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<(usize, MatchResult)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..args.threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        if index >= args.matches {
+                            break done;
+                        }
+                        let seed = args.seed.wrapping_add(index as u64);
+                        let swapped = index % 2 == 1;
+                        let result = play_match(
+                            &args.contestants,
+                            swapped,
+                            seed,
+                            args.max_ticks,
+                            units,
+                            cards,
+                        );
+                        done.push((index, result));
+                    }
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap())
+            .collect()
+    });
+    results.sort_by_key(|&(index, _)| index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
@@ -0,0 +1,169 @@
+use std::fmt::Write;
+
+use bevy::utils::HashMap;
+use common::{Card, CardsData};
+use serde::Serialize;
+
+use crate::{
+    run::{Contestant, MatchResult},
+    seconds_from_ticks, Format,
+};
+
+#[derive(Serialize)]
+pub struct Report {
+    matches: usize,
+    draws: usize,
+    avg_match_secs: f32,
+    contestants: Vec<ContestantReport>,
+    cards: Vec<CardReport>,
+}
+
+#[derive(Serialize)]
+struct ContestantReport {
+    deck: Vec<Card>,
+    bot: String,
+    wins: usize,
+    win_rate: f32,
+    avg_tower_damage: f32,
+}
+
+/// Урон по башням от юнитов, снарядов и заклинаний карты, включая юнитов
+/// из её построек и эффектов гибели
+#[derive(Serialize)]
+struct CardReport {
+    contestant: usize,
+    card: Card,
+    plays: usize,
+    elixir_spent: u32,
+    tower_damage: u32,
+    tower_damage_per_elixir: f32,
+}
+
+#[derive(Default)]
+struct CardTotals {
+    plays: usize,
+    elixir: u32,
+    damage: u32,
+}
+
+impl Report {
+    pub fn new(
+        contestants: &[Contestant; 2],
+        results: &[MatchResult],
+        cards: &CardsData,
+    ) -> Self {
+        let matches = results.len();
+        let ratio = |n: f32| match matches {
+            0 => 0.,
+            _ => n / matches as f32,
+        };
+        let total_ticks: u64 = results.iter().map(|r| r.ticks).sum();
+
+        let contestant_reports = contestants
+            .iter()
+            .enumerate()
+            .map(|(i, contestant)| {
+                let wins = results.iter().filter(|r| r.winner == Some(i)).count();
+                let damage: u32 = results.iter().map(|r| r.tower_damage[i]).sum();
+                ContestantReport {
+                    deck: contestant.deck.clone(),
+                    bot: format!("{:?}", contestant.difficulty),
+                    wins,
+                    win_rate: ratio(wins as f32),
+                    avg_tower_damage: ratio(damage as f32),
+                }
+            })
+            .collect();
+
+        let mut totals: HashMap<(usize, Card), CardTotals> = HashMap::new();
+        for result in results {
+            for &(i, card) in &result.plays {
+                let entry = totals.entry((i, card)).or_default();
+                entry.plays += 1;
+                entry.elixir += cards.stats(card).cost as u32;
+            }
+            for (&key, &damage) in &result.card_damage {
+                totals.entry(key).or_default().damage += damage;
+            }
+        }
+        // Порядок карт как в колодах, чтобы отчёт не зависел от HashMap
+        let card_reports = contestants
+            .iter()
+            .enumerate()
+            .flat_map(|(i, contestant)| contestant.deck.iter().map(move |&card| (i, card)))
+            .filter_map(|key| {
+                let totals = totals.remove(&key)?;
+                Some(CardReport {
+                    contestant: key.0,
+                    card: key.1,
+                    plays: totals.plays,
+                    elixir_spent: totals.elixir,
+                    tower_damage: totals.damage,
+                    tower_damage_per_elixir: totals.damage as f32
+                        / totals.elixir.max(1) as f32,
+                })
+            })
+            .collect();
+
+        Self {
+            matches,
+            draws: results.iter().filter(|r| r.winner.is_none()).count(),
+            avg_match_secs: ratio(seconds_from_ticks(total_ticks)),
+            contestants: contestant_reports,
+            cards: card_reports,
+        }
+    }
+
+    /// CSV состоит из двух таблиц через пустую строку: участники и карты
+    pub fn write(&self, format: Format) -> Result<String, String> {
+        match format {
+            Format::Json => serde_json::to_string_pretty(self).map_err(|e| e.to_string()),
+            Format::Csv => Ok(self.csv()),
+        }
+    }
+
+    fn csv(&self) -> String {
+        let mut out = String::new();
+        writeln!(
+            out,
+            "contestant,bot,deck,matches,wins,draws,win_rate,avg_tower_damage,avg_match_secs"
+        )
+        .unwrap();
+        for (i, c) in self.contestants.iter().enumerate() {
+            let deck: Vec<String> = c.deck.iter().map(|card| format!("{card:?}")).collect();
+            writeln!(
+                out,
+                "{i},{},{},{},{},{},{:.3},{:.1},{:.1}",
+                c.bot,
+                deck.join(" "),
+                self.matches,
+                c.wins,
+                self.draws,
+                c.win_rate,
+                c.avg_tower_damage,
+                self.avg_match_secs,
+            )
+            .unwrap();
+        }
+        writeln!(out).unwrap();
+        writeln!(
+            out,
+            "contestant,card,plays,elixir_spent,tower_damage,tower_damage_per_elixir"
+        )
+        .unwrap();
+        for c in &self.cards {
+            writeln!(
+                out,
+                "{},{:?},{},{},{},{:.2}",
+                c.contestant,
+                c.card,
+                c.plays,
+                c.elixir_spent,
+                c.tower_damage,
+                c.tower_damage_per_elixir,
+            )
+            .unwrap();
+        }
+        out
+    }
+}
@@ -0,0 +1,141 @@
+use bevy::{prelude::*, utils::HashMap};
+use common::{Card, CardsData, Level, PlayerNumber, Unit, UnitsData};
+use simulation::{Bot, CardPlay, Difficulty, HeadlessMatch, SimEvent};
+
+/// Колода и сложность бота одной из сторон сравнения
+#[derive(Clone)]
+pub struct Contestant {
+    pub deck: Vec<Card>,
+    pub difficulty: Difficulty,
+}
+
+/// Итог матча. Индексы участников, а не игроков, так как стороны меняются
+pub struct MatchResult {
+    pub winner: Option<usize>,
+    pub ticks: u64,
+    // Урон, нанесённый башням противника
+    pub tower_damage: [u32; 2],
+    // Разрушенные башни противника
+    pub crowns: [u8; 2],
+    pub plays: Vec<(usize, Card)>,
+    // Урон по башням от юнитов и заклинаний каждой карты участника
+    pub card_damage: HashMap<(usize, Card), u32>,
+}
+
+/// Участник 0 играет первым, если стороны не поменяны
+fn player_of(contestant: usize, swapped: bool) -> PlayerNumber {
+    match (contestant == 0) != swapped {
+        true => PlayerNumber::One,
+        false => PlayerNumber::Two,
+    }
+}
+
+pub fn play_match(
+    contestants: &[Contestant; 2],
+    swapped: bool,
+    seed: u64,
+    max_ticks: u64,
+    units: &UnitsData,
+    cards: &CardsData,
+) -> MatchResult {
+    let mut sim = HeadlessMatch::new(units.clone(), cards.clone(), seed);
+    let mut bots: Vec<Bot> = contestants
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            let bot_seed = seed ^ (i as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+            Bot::new(
+                player_of(i, swapped),
+                c.deck.clone(),
+                c.difficulty,
+                bot_seed,
+            )
+        })
+        .collect();
+    let contestant_of = |player: PlayerNumber| (player_of(1, swapped) == player) as usize;
+    // Розыгрыши одного тика применяются в порядке поступления. Первым решает
+    // первый игрок, чтобы при смене сторон менялась и очерёдность
+    let order = match swapped {
+        false => [0, 1],
+        true => [1, 0],
+    };
+
+    let mut result = MatchResult {
+        winner: None,
+        ticks: 0,
+        tower_damage: [0; 2],
+        crowns: [0; 2],
+        plays: Vec::new(),
+        card_damage: HashMap::new(),
+    };
+    // Башни и их владельцы, чтобы отличать урон по башням от остального
+    let mut towers: HashMap<Entity, PlayerNumber> = HashMap::new();
+    let mut events = sim.events();
+
+    loop {
+        for event in events {
+            match event {
+                SimEvent::UnitSpawned {
+                    entity,
+                    unit: Unit::ArcherTower | Unit::KingTower,
+                    owner,
+                    ..
+                } => {
+                    towers.insert(entity, owner);
+                }
+                SimEvent::UnitDied(entity) => {
+                    if let Some(owner) = towers.remove(&entity) {
+                        result.crowns[contestant_of(owner.opponent())] += 1;
+                    }
+                }
+                SimEvent::Damaged {
+                    entity,
+                    amount,
+                    source,
+                } => {
+                    if let Some(&owner) = towers.get(&entity) {
+                        let attacker = contestant_of(owner.opponent());
+                        result.tower_damage[attacker] += amount as u32;
+                        // Урон башен по башням ни одной карте не принадлежит
+                        if let Some(card) = source {
+                            *result.card_damage.entry((attacker, card)).or_default() +=
+                                amount as u32;
+                        }
+                    }
+                }
+                SimEvent::MatchEnded { winner } => result.winner = Some(contestant_of(winner)),
+                _ => {}
+            }
+        }
+        if result.winner.is_some() || sim.tick() >= max_ticks {
+            break;
+        }
+
+        for i in order {
+            let bot = &mut bots[i];
+            let Some((card, placement)) = sim.think(bot) else {
+                continue;
+            };
+            let play = CardPlay {
+                tick: sim.tick() + 1,
+                player: bot.player(),
+                card,
+                level: Level::default(),
+                placement,
+            };
+            // Те же правила, что у сервера: непринятый розыгрыш не считается
+            if sim.check(&play).is_err() {
+                continue;
+            }
+            sim.play(play);
+            result.plays.push((i, card));
+        }
+        events = sim.step();
+    }
+    result.ticks = sim.tick();
+    // По истечении времени побеждает тот, кто разрушил больше башен
+    if result.winner.is_none() && result.crowns[0] != result.crowns[1] {
+        result.winner = Some((result.crowns[1] > result.crowns[0]) as usize);
+    }
+    result
+}
@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+use common::load_balance;
+
+use crate::ServerSettings;
+
+pub(super) fn plugin(app: &mut App) {
+    let dir = app.world().resource::<ServerSettings>().assets.clone();
+    let (units, cards) = load_balance(&dir).unwrap_or_else(|e| panic!("{e}"));
+    app.insert_resource(units);
+    app.insert_resource(cards);
+
+    #[cfg(debug_assertions)]
+    {
+        app.insert_resource(BalanceWatcher {
+            timer: Timer::from_seconds(1., TimerMode::Repeating),
+            modified: last_modified(&dir),
+            dir,
+            deferred: false,
+        });
+        app.add_systems(Update, reload_balance);
+    }
+}
+
+/// В dev-сборках файлы баланса перечитываются при изменении.
+/// Новые значения применяются к юнитам, выставленным после этого.
+/// Пока идёт запись матча, баланс не меняется: в записи хеш баланса на начало матча
+#[cfg(debug_assertions)]
+#[derive(Resource)]
+struct BalanceWatcher {
+    timer: Timer,
+    modified: Option<std::time::SystemTime>,
+    dir: std::path::PathBuf,
+    // Изменение уже замечено, но отложено до конца записи
+    deferred: bool,
+}
+
+#[cfg(debug_assertions)]
+fn last_modified(dir: &std::path::Path) -> Option<std::time::SystemTime> {
+    use common::{CARDS_PATH, UNITS_PATH};
+
+    [UNITS_PATH, CARDS_PATH]
+        .iter()
+        .filter_map(|path| std::fs::metadata(dir.join(path)).ok())
+        .filter_map(|metadata| metadata.modified().ok())
+        .max()
+}
+
+#[cfg(debug_assertions)]
+fn reload_balance(
+    mut watcher: ResMut<BalanceWatcher>,
+    mut server: ResMut<bevy_quinnet::server::QuinnetServer>,
+    recorder: Res<crate::replay::ReplayRecorder>,
+    time: Res<Time>,
+    mut cmd: Commands,
+) {
+    use common::{ServerChannel, ServerMessage};
+
+    if !watcher.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    let modified = last_modified(&watcher.dir);
+    if modified == watcher.modified {
+        return;
+    }
+    if recorder.is_recording() {
+        if !watcher.deferred {
+            warn!("Баланс обновится после матча, пока идёт его запись");
+            watcher.deferred = true;
+        }
+        return;
+    }
+    watcher.deferred = false;
+    watcher.modified = modified;
+
+    // Ошибка в файле не должна ронять идущий матч
+    let (units, cards) = match load_balance(&watcher.dir) {
+        Ok(balance) => balance,
+        Err(e) => {
+            error!("Баланс не обновлён: {e}");
+            return;
+        }
+    };
+    info!("Баланс обновлён");
+
+    server
+        .endpoint_mut()
+        .broadcast_message_on(
+            ServerChannel::OrderedReliable,
+            ServerMessage::UpdateBalance {
+                units: units.clone(),
+                cards: cards.clone(),
+            },
+        )
+        .unwrap();
+    cmd.insert_resource(units);
+    cmd.insert_resource(cards);
+}
@@ -0,0 +1,69 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use common::{Level, PlayerNumber};
+use simulation::{Battlefield, Bot, BOT_DECK};
+
+use crate::{
+    networking::{handle_client_messages, CardInput, Lobby, LobbyFull},
+    ServerSettings,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ServerBot>();
+    app.add_systems(
+        Update,
+        (join_lobby, play_bot_cards.after(handle_client_messages)),
+    );
+}
+
+/// Бот, занявший место в лобби
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct ServerBot(Option<Bot>);
+
+fn join_lobby(
+    lobby: Res<Lobby>,
+    settings: Res<ServerSettings>,
+    mut bot: ResMut<ServerBot>,
+    mut input: CardInput,
+    time: Res<Time>,
+    // Сколько ждёт единственный игрок
+    mut waited: Local<f32>,
+    mut cmd: Commands,
+) {
+    let Some(difficulty) = settings.bot else {
+        return;
+    };
+    if lobby.len() != 1 || bot.is_some() {
+        *waited = 0.;
+        return;
+    }
+    *waited += time.delta_secs();
+    if *waited < settings.bot_delay {
+        return;
+    }
+
+    let player = match lobby.values().next() {
+        Some(PlayerNumber::One) => PlayerNumber::Two,
+        _ => PlayerNumber::One,
+    };
+    let seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64);
+    let deck = BOT_DECK.iter().map(|&card| (card, Level::default()));
+    input.set_deck(player, deck.collect());
+    **bot = Some(Bot::new(player, BOT_DECK.to_vec(), difficulty, seed));
+    info!("Второй игрок не подключился, играет бот ({difficulty:?})");
+    cmd.trigger(LobbyFull);
+}
+
+fn play_bot_cards(mut bot: ResMut<ServerBot>, field: Battlefield, mut input: CardInput) {
+    let Some(bot) = bot.0.as_mut() else {
+        return;
+    };
+    if let Some((card, placement)) = bot.think(&field) {
+        if let Err(e) = input.play(bot.player(), card, placement) {
+            warn!("Бот: {e}");
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Сервер матча. Собран библиотекой, чтобы интеграционные тесты
+//! поднимали те же плагины, что и бинарник
+
+use std::{env, path::PathBuf};
+
+use bevy::prelude::*;
+use common::SERVER_PORT;
+use simulation::Difficulty;
+
+mod balance;
+mod bot;
+mod networking;
+mod replay;
+
+pub use bot::ServerBot;
+pub use networking::{Decks, Lobby};
+
+/// Плагины сервера без MinimalPlugins и логов. Настройки по умолчанию,
+/// если ServerSettings не вставлены заранее
+pub fn plugin(app: &mut App) {
+    app.init_resource::<ServerSettings>();
+
+    app.add_plugins((
+        balance::plugin,
+        simulation::plugin,
+        networking::plugin,
+        replay::plugin,
+        bot::plugin,
+    ));
+}
+
+#[derive(Resource, Clone, Debug)]
+pub struct ServerSettings {
+    pub port: u16,
+    // None отключает бота
+    pub bot: Option<Difficulty>,
+    // Сколько секунд ждать второго игрока, прежде чем его место займёт бот
+    pub bot_delay: f32,
+    // Папка с файлами баланса. Относительный путь считается
+    // от рабочей папки сервера
+    pub assets: PathBuf,
+    // None отключает сохранение записей. Относительный путь считается
+    // от рабочей папки сервера
+    pub replays: Option<PathBuf>,
+}
+impl Default for ServerSettings {
+    fn default() -> Self {
+        Self {
+            port: SERVER_PORT,
+            bot: Some(Difficulty::default()),
+            bot_delay: 10.,
+            assets: PathBuf::from("assets"),
+            replays: Some(PathBuf::from("replays")),
+        }
+    }
+}
+impl ServerSettings {
+    /// Сложность бота задаётся через BOYAR_BOT: easy, normal или hard, off отключает бота.
+    /// Папки с балансом и для записей можно переопределить через BOYAR_ASSETS и BOYAR_REPLAYS
+    pub fn from_env() -> Self {
+        let mut settings = Self::default();
+        match env::var("BOYAR_BOT") {
+            Err(_) => {}
+            Ok(value) if value == "off" => settings.bot = None,
+            Ok(value) => settings.bot = value.parse().map_err(|e| warn!("{e}")).ok(),
+        }
+        if let Some(dir) = env::var_os("BOYAR_ASSETS") {
+            settings.assets = PathBuf::from(dir);
+        }
+        if let Some(dir) = env::var_os("BOYAR_REPLAYS") {
+            settings.replays = Some(PathBuf::from(dir));
+        }
+        settings
+    }
+}
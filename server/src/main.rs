@@ -1,19 +1,10 @@
 use bevy::{log::LogPlugin, prelude::*};
-
-mod ai;
-mod networking;
-mod projectiles;
-mod units;
+use server::ServerSettings;
 
 fn main() {
     App::new()
-        .add_plugins((
-            MinimalPlugins,
-            LogPlugin::default(),
-            ai::plugin,
-            units::plugin,
-            projectiles::plugin,
-            networking::plugin,
-        ))
+        .add_plugins((MinimalPlugins, LogPlugin::default()))
+        .insert_resource(ServerSettings::from_env())
+        .add_plugins(server::plugin)
         .run();
 }
@@ -1,6 +1,6 @@
-use core::f32;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-use bevy::{prelude::*, utils::HashMap};
+use bevy::{ecs::system::SystemParam, prelude::*, utils::HashMap};
 use bevy_quinnet::{
     server::{
         certificate::CertificateRetrievalMode, ConnectionEvent, QuinnetServer,
@@ -9,29 +9,32 @@ use bevy_quinnet::{
     shared::ClientId,
 };
 use common::{
-    ArenaPos, Card, ClientMessage, Direction, Health, PlayerNumber, ServerChannel,
-    ServerMessage, Unit, UnitState, LOCAL_BIND_IP, SERVER_HOST, SERVER_PORT,
+    balance_hash, ArenaPos, Card, CardsData, ClientMessage, Level, PlayerNumber,
+    ServerChannel, ServerMessage, UnitsData, LOCAL_BIND_IP, SERVER_HOST,
 };
-
-use crate::{
-    ai::{Attack, Movement, StunnedTimer},
-    units::{Giant, SpawnUnit},
+use simulation::{
+    check_play, CardPlay, Elixir, PendingPlays, Recipients, SimEvent, SimRng, Snapshot,
+    StartMatch, Tick,
 };
 
+use crate::{bot::ServerBot, replay::ReplayRecorder, ServerSettings};
+
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins(QuinnetServerPlugin::default());
 
     app.init_resource::<Lobby>();
+    app.init_resource::<Decks>();
     app.add_systems(Startup, start_listening);
     app.add_systems(Update, (handle_connection_events, handle_client_messages));
+    app.add_observer(start_game);
 
-    app.add_systems(FixedPostUpdate, sync_entities);
+    app.add_systems(FixedPostUpdate, (forward_sim_events, sync_entities));
 }
 
-fn start_listening(mut server: ResMut<QuinnetServer>) {
+fn start_listening(mut server: ResMut<QuinnetServer>, settings: Res<ServerSettings>) {
     server
         .start_endpoint(
-            ServerEndpointConfiguration::from_ip(LOCAL_BIND_IP, SERVER_PORT),
+            ServerEndpointConfiguration::from_ip(LOCAL_BIND_IP, settings.port),
             CertificateRetrievalMode::GenerateSelfSigned {
                 server_hostname: SERVER_HOST.to_string(),
             },
@@ -43,21 +46,28 @@ fn start_listening(mut server: ResMut<QuinnetServer>) {
 #[derive(Resource, Default, Deref, DerefMut)]
 pub struct Lobby(HashMap<ClientId, PlayerNumber>);
 
+/// Колоды игроков с уровнями карт.
+/// Уровни пока присылает сам клиент, сервер только ограничивает их таблицей
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct Decks(HashMap<PlayerNumber, HashMap<Card, Level>>);
+
 fn handle_connection_events(
     mut connection_events: EventReader<ConnectionEvent>,
     mut lobby: ResMut<Lobby>,
+    bot: Res<ServerBot>,
     mut server: ResMut<QuinnetServer>,
     mut cmd: Commands,
 ) {
-    let lobby_len = lobby.len() as u8;
     for client in connection_events.read() {
-        if lobby_len >= 2 {
+        // Место второго игрока может занимать бот
+        let occupied = lobby.len() + bot.is_some() as usize;
+        if occupied >= 2 {
             server.endpoint_mut().disconnect_client(client.id).unwrap();
             continue;
         }
         use PlayerNumber::*;
 
-        let player_num = match lobby_len {
+        let player_num = match occupied {
             0 => One,
             1 => Two,
             _ => unreachable!(),
@@ -65,200 +75,152 @@ fn handle_connection_events(
         lobby.insert(client.id, player_num);
 
         if lobby.len() == 2 {
-            // Отправить каждому игроку его PlayerNumber
-            for (client_id, player_num) in lobby.iter() {
-                server
-                    .endpoint_mut()
-                    .send_message_on(
-                        *client_id,
-                        ServerChannel::OrderedReliable,
-                        ServerMessage::StartGame(*player_num),
-                    )
-                    .unwrap();
-            }
-
-            Unit::ArcherTower.spawn(ArenaPos(-5.5, -9.5), One, &mut cmd);
-            Unit::KingTower.spawn(ArenaPos(0., -13.), One, &mut cmd);
-            Unit::ArcherTower.spawn(ArenaPos(5.5, -9.5), One, &mut cmd);
-
-            Unit::ArcherTower.spawn(ArenaPos(-5.5, 9.5), Two, &mut cmd);
-            Unit::KingTower.spawn(ArenaPos(0., 13.), Two, &mut cmd);
-            Unit::ArcherTower.spawn(ArenaPos(5.5, 9.5), Two, &mut cmd);
+            cmd.trigger(LobbyFull);
         }
     }
 }
 
-fn handle_client_messages(
-    mut server: ResMut<QuinnetServer>,
+/// Оба места в лобби заняты, людьми или ботом
+#[derive(Event)]
+pub struct LobbyFull;
+
+fn start_game(
+    _trigger: Trigger<LobbyFull>,
     lobby: Res<Lobby>,
+    mut server: ResMut<QuinnetServer>,
+    mut recorder: ResMut<ReplayRecorder>,
+    units: Res<UnitsData>,
+    cards: Res<CardsData>,
     mut cmd: Commands,
 ) {
-    let endpoint = server.endpoint_mut();
-    for client_id in endpoint.clients() {
-        while let Some((_, message)) =
-            endpoint.try_receive_message_from::<ClientMessage>(client_id)
-        {
-            let player_num = lobby.get(&client_id).unwrap();
-            match message {
-                ClientMessage::PlayCard { card, placement } => match card {
-                    Card::Rus => Unit::Rus.spawn(placement, *player_num, &mut cmd),
-                    Card::Musketeer => Unit::Musketeer.spawn(placement, *player_num, &mut cmd),
-                    Card::ThreeMusketeers => {
-                        let ArenaPos(x, y) = placement;
-                        Unit::Musketeer.spawn(ArenaPos(x, y + 0.8), *player_num, &mut cmd);
-                        Unit::Musketeer.spawn(ArenaPos(x + 0.8, y), *player_num, &mut cmd);
-                        Unit::Musketeer.spawn(ArenaPos(x - 0.8, y), *player_num, &mut cmd);
-                    }
-                    Card::Bats => {
-                        let ArenaPos(x, y) = placement;
-                        Unit::Bat.spawn(ArenaPos(x, y + 0.8), *player_num, &mut cmd);
-                        Unit::Bat.spawn(ArenaPos(x + 0.8, y), *player_num, &mut cmd);
-                        Unit::Bat.spawn(ArenaPos(x - 0.8, y), *player_num, &mut cmd);
-                    }
-                    Card::BatHorde => {
-                        let ArenaPos(x, y) = placement;
-                        Unit::Bat.spawn(ArenaPos(x + 0.5, y + 0.5), *player_num, &mut cmd);
-                        Unit::Bat.spawn(ArenaPos(x + 0.8, y), *player_num, &mut cmd);
-                        Unit::Bat.spawn(ArenaPos(x + 0.5, y - 0.5), *player_num, &mut cmd);
-                        Unit::Bat.spawn(ArenaPos(x - 0.5, y - 0.5), *player_num, &mut cmd);
-                        Unit::Bat.spawn(ArenaPos(x - 0.8, y), *player_num, &mut cmd);
-                        Unit::Bat.spawn(ArenaPos(x - 0.5, y + 0.5), *player_num, &mut cmd);
-                    }
-                    Card::Priest => Unit::Priest.spawn(placement, *player_num, &mut cmd),
-                    Card::Bomber => Unit::Bomber.spawn(placement, *player_num, &mut cmd),
-                    Card::Giant => Unit::Giant.spawn(placement, *player_num, &mut cmd),
-                },
-            }
-        }
+    // Отправить каждому игроку его PlayerNumber
+    for (client_id, player_num) in lobby.iter() {
+        server
+            .endpoint_mut()
+            .send_message_on(
+                *client_id,
+                ServerChannel::OrderedReliable,
+                ServerMessage::StartGame(*player_num),
+            )
+            .unwrap();
     }
+
+    // Seed нужен только для воспроизведения матча, подойдёт текущее время
+    let seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64);
+    cmd.insert_resource(SimRng::new(seed));
+    recorder.start(seed, balance_hash(&units, &cards));
+    cmd.trigger(StartMatch);
 }
 
-trait DefaultDirection {
-    fn default_direction(&self) -> Direction;
+/// Единственный путь, которым карты попадают в симуляцию, и от людей, и от бота
+#[derive(SystemParam)]
+pub struct CardInput<'w> {
+    decks: ResMut<'w, Decks>,
+    tick: Res<'w, Tick>,
+    elixir: Res<'w, Elixir>,
+    cards: Res<'w, CardsData>,
+    pending: ResMut<'w, PendingPlays>,
+    recorder: ResMut<'w, ReplayRecorder>,
 }
-impl DefaultDirection for PlayerNumber {
-    fn default_direction(&self) -> Direction {
-        match self {
-            PlayerNumber::One => Direction::Up,
-            PlayerNumber::Two => Direction::Down,
-        }
+impl CardInput<'_> {
+    pub fn set_deck(&mut self, player_num: PlayerNumber, deck: HashMap<Card, Level>) {
+        self.decks.insert(player_num, deck);
     }
-}
 
-fn calc_direction(direction: &ArenaPos) -> Direction {
-    let mut angle = direction.0.acos() * 180. / f32::consts::PI;
-    if direction.1 < 0. {
-        angle = -angle + 360.;
+    /// Карта из колоды игрока срабатывает на следующем шаге симуляции.
+    /// Розыгрыш не из колоды, без эликсира или не на своей половине не записывается
+    pub fn play(
+        &mut self,
+        player_num: PlayerNumber,
+        card: Card,
+        placement: ArenaPos,
+    ) -> Result<(), String> {
+        let Some(&level) = self.decks.get(&player_num).and_then(|deck| deck.get(&card)) else {
+            return Err(format!("карта {card:?} не из колоды"));
+        };
+        let play = CardPlay {
+            tick: self.tick.0 + 1,
+            player: player_num,
+            card,
+            level,
+            placement,
+        };
+        check_play(&play, &self.elixir, &self.pending, &self.cards)?;
+        self.recorder.record(&play);
+        self.pending.push(play);
+        Ok(())
     }
 
-    match angle {
-        0.0..20. | 340.0..360. => Direction::Right,
-        20.0..160. => Direction::Up,
-        160.0..200. => Direction::Left,
-        200.0..340. => Direction::Down,
-        _ => Direction::Right,
+    pub fn elixir(&self, player_num: PlayerNumber) -> f32 {
+        self.elixir.amount(player_num)
     }
 }
 
-fn sync_entities(
-    units: Query<(
-        Entity,
-        &ArenaPos,
-        &UnitState,
-        &Attack,
-        Option<&Movement>,
-        &PlayerNumber,
-        &Health,
-        Option<&StunnedTimer>,
-    )>,
-    giants: Query<(
-        Entity,
-        &ArenaPos,
-        &UnitState,
-        &Giant,
-        &Movement,
-        &PlayerNumber,
-        &Health,
-        Option<&StunnedTimer>,
-    )>,
-    projectiles: Query<(Entity, &ArenaPos), Without<PlayerNumber>>,
-    positions: Query<&ArenaPos>,
+pub(super) fn handle_client_messages(
     mut server: ResMut<QuinnetServer>,
+    lobby: Res<Lobby>,
+    mut input: CardInput,
 ) {
-    let mut u = Vec::new();
-    for (entity, pos, state, attack, movement, player_num, health, stun) in &units {
-        let direction = match state {
-            UnitState::Idle => player_num.default_direction(),
-            UnitState::Moving => {
-                let movement = movement.unwrap();
-                match movement.target {
-                    Some(m) => {
-                        let Ok(target_pos) = positions.get(m) else {
-                            continue;
-                        };
-                        calc_direction(&pos.direction(target_pos))
-                    }
-                    None => player_num.default_direction(),
-                }
-            }
-            UnitState::Attacking => match attack.target {
-                Some(a) => {
-                    let Ok(target_pos) = positions.get(a) else {
-                        continue;
-                    };
-                    calc_direction(&pos.direction(target_pos))
+    let endpoint = server.endpoint_mut();
+    for client_id in endpoint.clients() {
+        while let Some((_, message)) =
+            endpoint.try_receive_message_from::<ClientMessage>(client_id)
+        {
+            let player_num = *lobby.get(&client_id).unwrap();
+            match message {
+                ClientMessage::SetDeck(deck) => {
+                    let deck = deck
+                        .into_iter()
+                        .map(|(card, level)| (card, Level::new(level.0)))
+                        .collect();
+                    input.set_deck(player_num, deck);
                 }
-                None => player_num.default_direction(),
-            },
-        };
-        let mut state = *state;
-        if let Some(_) = stun {
-            state = UnitState::Idle
-        }
-        u.push((entity, *pos, direction, state, *health));
-    }
-    for (entity, pos, state, giant, movement, player_num, health, stun) in &giants {
-        let direction = match state {
-            UnitState::Idle => player_num.default_direction(),
-            UnitState::Moving => match movement.target {
-                Some(m) => {
-                    let Ok(target_pos) = positions.get(m) else {
+                ClientMessage::PlayCard { card, placement } => {
+                    let Err(e) = input.play(player_num, card, placement) else {
                         continue;
                     };
-                    calc_direction(&pos.direction(target_pos))
-                }
-                None => player_num.default_direction(),
-            },
-            UnitState::Attacking => match giant.target {
-                Some(a) => {
-                    let Ok(target_pos) = positions.get(a) else {
-                        continue;
+                    warn!("Игрок {player_num:?}: {e}");
+                    let rejected = ServerMessage::PlayRejected {
+                        card,
+                        elixir: input.elixir(player_num),
                     };
-                    calc_direction(&pos.direction(target_pos))
+                    endpoint
+                        .send_message_on(client_id, ServerChannel::OrderedReliable, rejected)
+                        .unwrap();
                 }
-                None => player_num.default_direction(),
-            },
-        };
-        let mut state = *state;
-        if let Some(_) = stun {
-            state = UnitState::Idle
+            }
         }
-        u.push((entity, *pos, direction, state, *health));
     }
+}
 
-    let mut p = Vec::new();
-    for (entity, position) in &projectiles {
-        p.push((entity, *position));
+/// Переводит события симуляции в сообщения клиентам
+fn forward_sim_events(
+    mut events: EventReader<SimEvent>,
+    lobby: Res<Lobby>,
+    mut server: ResMut<QuinnetServer>,
+) {
+    let endpoint = server.endpoint_mut();
+    for (recipients, message) in events.read().filter_map(SimEvent::message) {
+        match recipients {
+            Recipients::All => {
+                endpoint.broadcast_message_on(ServerChannel::OrderedReliable, message)
+            }
+            Recipients::Player(player) => {
+                let Some((&client_id, _)) = lobby.iter().find(|(_, &p)| p == player) else {
+                    continue;
+                };
+                endpoint.send_message_on(client_id, ServerChannel::OrderedReliable, message)
+            }
+        }
+        .unwrap();
     }
+}
 
+fn sync_entities(snapshot: Snapshot, mut server: ResMut<QuinnetServer>) {
     server
         .endpoint_mut()
-        .broadcast_message_on(
-            ServerChannel::Unreliable,
-            ServerMessage::SyncEntities {
-                units: u,
-                projectiles: p,
-            },
-        )
+        .broadcast_message_on(ServerChannel::Unreliable, snapshot.message())
         .unwrap();
 }
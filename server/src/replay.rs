@@ -0,0 +1,130 @@
+use std::{
+    fs,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::prelude::*;
+use bevy_quinnet::server::ConnectionLostEvent;
+use common::{
+    PlayerNumber, Replay, ReplayHeader, ReplayInput, PROTOCOL_VERSION, REPLAY_EXTENSION,
+};
+use simulation::{CardPlay, SimEvent, Tick};
+
+use crate::{networking::Decks, ServerSettings};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ReplayRecorder>();
+    app.add_systems(FixedPostUpdate, save_on_match_end);
+    app.add_systems(Update, save_on_disconnect);
+}
+
+/// Записывает принятые розыгрыши текущего матча.
+/// Колоды берутся из Decks при сохранении, так как клиенты присылают их после начала
+#[derive(Resource, Default)]
+pub struct ReplayRecorder(Option<Recording>);
+
+struct Recording {
+    balance_hash: u64,
+    seed: u64,
+    inputs: Vec<ReplayInput>,
+}
+
+impl ReplayRecorder {
+    pub fn start(&mut self, seed: u64, balance_hash: u64) {
+        self.0 = Some(Recording {
+            balance_hash,
+            seed,
+            inputs: Vec::new(),
+        });
+    }
+
+    // Нужно только перезагрузке баланса в dev-сборках
+    #[cfg(debug_assertions)]
+    pub fn is_recording(&self) -> bool {
+        self.0.is_some()
+    }
+
+    pub fn record(&mut self, play: &CardPlay) {
+        let Some(recording) = &mut self.0 else {
+            return;
+        };
+        recording.inputs.push(ReplayInput {
+            tick: play.tick,
+            player: play.player,
+            card: play.card,
+            placement: play.placement,
+        });
+    }
+
+    fn finish(&mut self, decks: &Decks, length: u64) -> Option<Replay> {
+        let recording = self.0.take()?;
+        let deck = |player| {
+            decks
+                .get(&player)
+                .map(|deck| deck.iter().map(|(&card, &level)| (card, level)).collect())
+                .unwrap_or_default()
+        };
+        Some(Replay {
+            header: ReplayHeader {
+                protocol: PROTOCOL_VERSION,
+                balance_hash: recording.balance_hash,
+                seed: recording.seed,
+                decks: [deck(PlayerNumber::One), deck(PlayerNumber::Two)],
+            },
+            inputs: recording.inputs,
+            length,
+        })
+    }
+}
+
+fn save_on_match_end(
+    mut events: EventReader<SimEvent>,
+    mut recorder: ResMut<ReplayRecorder>,
+    decks: Res<Decks>,
+    tick: Res<Tick>,
+    settings: Res<ServerSettings>,
+) {
+    for event in events.read() {
+        if let SimEvent::MatchEnded { .. } = event {
+            if let Some(replay) = recorder.finish(&decks, tick.0) {
+                save(&replay, settings.replays.as_deref());
+            }
+        }
+    }
+}
+
+/// Матч, брошенный игроком, тоже сохраняется
+fn save_on_disconnect(
+    mut lost: EventReader<ConnectionLostEvent>,
+    mut recorder: ResMut<ReplayRecorder>,
+    decks: Res<Decks>,
+    tick: Res<Tick>,
+    settings: Res<ServerSettings>,
+) {
+    if lost.read().count() == 0 {
+        return;
+    }
+    if let Some(replay) = recorder.finish(&decks, tick.0) {
+        save(&replay, settings.replays.as_deref());
+    }
+}
+
+fn save(replay: &Replay, dir: Option<&Path>) {
+    let Some(dir) = dir else {
+        return;
+    };
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let path = dir.join(format!("{secs}.{REPLAY_EXTENSION}"));
+
+    let result = fs::create_dir_all(dir).and_then(|_| fs::write(&path, replay.encode()));
+    match result {
+        Ok(()) => info!("Запись матча сохранена в {}", path.display()),
+        Err(e) => error!(
+            "Не удалось сохранить запись матча в {}: {e}",
+            path.display()
+        ),
+    }
+}
@@ -0,0 +1,170 @@
+use common::{
+    ArenaPos, Card, Checksum, ChecksumEntry, Health, PlayerNumber, ServerMessage, Unit,
+    UnitState, CHECKSUM_INTERVAL, ELIXIR_INTERVAL, MAX_ELIXIR,
+};
+
+use crate::harness::{secs_to_ticks, settings, TestMatch};
+
+/// Два игрока в лобби, колоды отправлены
+fn started(port: u16) -> TestMatch {
+    let mut game = TestMatch::new(settings(port));
+    let first = game.join();
+    let second = game.join();
+    game.wait_until("матч начался", |m| {
+        m.clients.iter().all(|c| c.player().is_some())
+    });
+    game.send_deck(first);
+    game.send_deck(second);
+    game
+}
+
+/// Эликсир копится с нуля, полного хватит на любую карту колоды
+fn full_elixir(port: u16) -> TestMatch {
+    let mut game = started(port);
+    game.run_ticks(secs_to_ticks(MAX_ELIXIR as f32 * ELIXIR_INTERVAL));
+    game
+}
+
+#[test]
+fn card_outside_deck_is_rejected() {
+    let mut game = full_elixir(51101);
+    // Priest нет в колоде бота, которую отправляют тесты
+    game.play(0, Card::Priest, ArenaPos(0., -5.));
+    game.play(0, Card::Rus, ArenaPos(0., -5.));
+    game.wait_until("Rus появился", |m| {
+        !m.find(Unit::Rus, PlayerNumber::One).is_empty()
+    });
+
+    assert!(game.find(Unit::Priest, PlayerNumber::One).is_empty());
+}
+
+#[test]
+fn rus_at_bridge_reaches_princess_tower() {
+    let mut game = full_elixir(51102);
+    game.play(0, Card::Rus, ArenaPos(-5.5, -1.5));
+    game.wait_until("Rus появился", |m| {
+        !m.find(Unit::Rus, PlayerNumber::One).is_empty()
+    });
+    let spawned = game.tick();
+
+    // Развёртывание 1 с и около 7 клеток пути до дальности атаки со скоростью 2
+    let limit = spawned + secs_to_ticks(5.);
+    while game.tick() < limit {
+        game.step();
+        let rus = game.find(Unit::Rus, PlayerNumber::One);
+        if rus
+            .first()
+            .is_some_and(|rus| rus.state == UnitState::Attacking)
+        {
+            break;
+        }
+    }
+
+    let rus = game.find(Unit::Rus, PlayerNumber::One)[0];
+    assert_eq!(rus.state, UnitState::Attacking, "{rus:?}");
+    let tower = game
+        .find(Unit::ArcherTower, PlayerNumber::Two)
+        .into_iter()
+        .find(|t| t.pos.0 < 0.)
+        .unwrap();
+    assert!(rus.pos.distance(&tower.pos) < 3.5, "{rus:?} {tower:?}");
+
+    game.run_ticks(secs_to_ticks(1.));
+    let hit = game
+        .units()
+        .into_iter()
+        .find(|u| u.entity == tower.entity)
+        .unwrap();
+    assert!(hit.health.0 < tower.health.0, "{hit:?}");
+}
+
+#[test]
+fn destroyed_tower_is_reported_once() {
+    let mut game = started(51103);
+    let tower = game.find(Unit::ArcherTower, PlayerNumber::Two)[0].entity;
+    game.server
+        .world_mut()
+        .entity_mut(tower)
+        .insert(Health(0, 1400));
+    game.run_ticks(secs_to_ticks(1.));
+    game.flush();
+
+    assert!(game.server.world().get_entity(tower).is_err());
+    for client in &game.clients {
+        let died = client.count(|m| matches!(m, ServerMessage::UnitDied(e) if *e == tower));
+        let despawned =
+            client.count(|m| matches!(m, ServerMessage::Despawn(e) if *e == tower));
+        assert_eq!((died, despawned), (1, 0));
+    }
+}
+
+#[test]
+fn sync_carries_checksum() {
+    let mut game = full_elixir(51104);
+    game.play(0, Card::Rus, ArenaPos(-5.5, -1.5));
+    game.run_ticks(secs_to_ticks(3.));
+    game.flush();
+
+    let mut checked = 0;
+    for message in game.clients[0].received() {
+        let ServerMessage::SyncEntities {
+            units,
+            checksum: Some(checksum),
+            ..
+        } = message
+        else {
+            continue;
+        };
+        assert_eq!(checksum.tick % CHECKSUM_INTERVAL, 0);
+        // Клиент, у которого юниты совпадают с сервером, получает ту же сумму
+        let mut entries: Vec<_> = units
+            .iter()
+            .map(|&(entity, pos, _, state, health, ..)| {
+                ChecksumEntry::new(entity, pos, state, health)
+            })
+            .collect();
+        assert_eq!(Checksum::new(checksum.tick, &mut entries), *checksum);
+        checked += 1;
+    }
+    assert!(checked > 0, "Ни одного SyncEntities с контрольной суммой");
+}
+
+#[test]
+fn card_without_elixir_is_rejected() {
+    let mut game = started(51105);
+    // В начале матча эликсира нет, а Rus стоит 3
+    game.play(0, Card::Rus, ArenaPos(-5.5, -5.5));
+    game.wait_until("хватает эликсира на Musketeer", |m| {
+        m.elixir(PlayerNumber::One) >= 4.
+    });
+    game.play(0, Card::Musketeer, ArenaPos(5.5, -5.5));
+    game.wait_until("Musketeer появился", |m| {
+        !m.find(Unit::Musketeer, PlayerNumber::One).is_empty()
+    });
+
+    assert!(game.find(Unit::Rus, PlayerNumber::One).is_empty());
+    let rejected = game.clients[0].received().iter().find_map(|m| match m {
+        ServerMessage::PlayRejected {
+            card: Card::Rus,
+            elixir,
+        } => Some(*elixir),
+        _ => None,
+    });
+    assert!(rejected.is_some_and(|elixir| elixir < 3.), "{rejected:?}");
+}
+
+#[test]
+fn unit_on_enemy_half_is_rejected() {
+    let mut game = full_elixir(51106);
+    game.play(0, Card::Rus, ArenaPos(-5.5, 5.5));
+    game.play(0, Card::Rus, ArenaPos(-5.5, -5.5));
+    game.wait_until("Rus появился", |m| {
+        !m.find(Unit::Rus, PlayerNumber::One).is_empty()
+    });
+
+    let rus = game.find(Unit::Rus, PlayerNumber::One);
+    assert_eq!(rus.len(), 1, "{rus:?}");
+    assert!(rus[0].pos.1 < 0., "{rus:?}");
+    let rejected = game.clients[0].count(|m| matches!(m, ServerMessage::PlayRejected { .. }));
+    assert_eq!(rejected, 1);
+}
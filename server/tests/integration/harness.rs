@@ -0,0 +1,260 @@
+//! Сервер и клиенты без графики в одном процессе, соединённые через QUIC на localhost.
+//! Кадр сервера равен ровно одному тику симуляции, поэтому игровое время
+//! в тестах меряется тиками и не зависит от скорости машины
+
+use std::{
+    net::Ipv4Addr,
+    path::Path,
+    thread,
+    time::{Duration, Instant},
+};
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+use bevy_quinnet::client::{
+    certificate::CertificateVerificationMode, connection::ClientEndpointConfiguration,
+    QuinnetClient, QuinnetClientPlugin,
+};
+use common::{
+    ArenaPos, Card, ClientChannel, ClientMessage, Health, Level, PlayerNumber, ServerMessage,
+    Unit, UnitState, LOCAL_BIND_IP,
+};
+use server::{Lobby, ServerSettings};
+use simulation::{Elixir, Tick, BOT_DECK, TICK};
+
+/// Сколько реального времени ждать сетевых событий
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Сервер без бота и без записи матчей, баланс берётся из репозитория. У каждого теста свой порт,
+/// чтобы тесты шли параллельно
+pub fn settings(port: u16) -> ServerSettings {
+    ServerSettings {
+        port,
+        bot: None,
+        assets: Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets"),
+        replays: None,
+        ..default()
+    }
+}
+
+pub fn secs_to_ticks(secs: f32) -> u64 {
+    (secs / TICK.as_secs_f32()).round() as u64
+}
+
+/// Юнит на сервере, как его видит тест
+#[derive(Clone, Copy, Debug)]
+pub struct UnitInfo {
+    pub entity: Entity,
+    pub unit: Unit,
+    pub owner: PlayerNumber,
+    pub pos: ArenaPos,
+    pub state: UnitState,
+    pub health: Health,
+}
+
+pub struct TestMatch {
+    pub server: App,
+    pub clients: Vec<TestClient>,
+    port: u16,
+}
+impl TestMatch {
+    pub fn new(settings: ServerSettings) -> Self {
+        let port = settings.port;
+        let mut server = App::new();
+        server
+            .add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(TICK))
+            .insert_resource(settings)
+            .add_plugins(server::plugin);
+        server.finish();
+        server.cleanup();
+        // Startup открывает endpoint до подключения клиентов
+        server.update();
+
+        Self {
+            server,
+            clients: Vec::new(),
+            port,
+        }
+    }
+
+    /// Открывает соединение, не дожидаясь ответа сервера. Возвращает номер клиента
+    pub fn connect(&mut self) -> usize {
+        self.clients.push(TestClient::new(self.port));
+        self.clients.len() - 1
+    }
+
+    /// Подключает клиента и ждёт, пока сервер займёт им место в лобби.
+    /// Так порядок игроков в лобби совпадает с порядком вызовов
+    pub fn join(&mut self) -> usize {
+        let lobby = self.lobby_len();
+        let index = self.connect();
+        self.wait_until("клиент попал в лобби", |m| {
+            m.lobby_len() > lobby
+        });
+        index
+    }
+
+    /// Клиент выбирает колоду бота, как это делает настоящий клиент после StartGame
+    pub fn send_deck(&mut self, client: usize) {
+        let deck = BOT_DECK.iter().map(|&card| (card, Level::default()));
+        self.clients[client].send(ClientMessage::SetDeck(deck.collect()));
+    }
+
+    /// Розыгрыш срабатывает, когда сообщение дойдёт до сервера
+    pub fn play(&mut self, client: usize, card: Card, placement: ArenaPos) {
+        self.clients[client].send(ClientMessage::PlayCard { card, placement });
+    }
+
+    /// Один тик сервера и кадр каждого клиента
+    pub fn step(&mut self) {
+        self.server.update();
+        for client in &mut self.clients {
+            client.app.update();
+        }
+    }
+
+    /// Шаги до выполнения условия. Между шагами отдаёт время сетевым потокам.
+    /// Паникует с описанием ожидаемого, если реальное время вышло
+    pub fn wait_until(&mut self, what: &str, mut condition: impl FnMut(&mut Self) -> bool) {
+        let start = Instant::now();
+        while !condition(self) {
+            assert!(start.elapsed() < TIMEOUT, "Не дождались: {what}");
+            self.step();
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    /// Ровно ticks тиков сервера
+    pub fn run_ticks(&mut self, ticks: u64) {
+        for _ in 0..ticks {
+            self.step();
+        }
+    }
+
+    /// Даёт доставить сообщения, уже отправленные сервером, не продвигая матч
+    pub fn flush(&mut self) {
+        let start = Instant::now();
+        while start.elapsed() < Duration::from_millis(200) {
+            for client in &mut self.clients {
+                client.app.update();
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    pub fn tick(&self) -> u64 {
+        self.server.world().resource::<Tick>().0
+    }
+
+    pub fn elixir(&self, player: PlayerNumber) -> f32 {
+        self.server.world().resource::<Elixir>().amount(player)
+    }
+
+    pub fn lobby_len(&self) -> usize {
+        self.server.world().resource::<Lobby>().len()
+    }
+
+    pub fn units(&mut self) -> Vec<UnitInfo> {
+        let world = self.server.world_mut();
+        let mut query =
+            world.query::<(Entity, &Unit, &PlayerNumber, &ArenaPos, &UnitState, &Health)>();
+        let mut units: Vec<_> = query
+            .iter(world)
+            .map(|(entity, unit, owner, pos, state, health)| UnitInfo {
+                entity,
+                unit: *unit,
+                owner: *owner,
+                pos: *pos,
+                state: *state,
+                health: *health,
+            })
+            .collect();
+        units.sort_unstable_by_key(|info| info.entity);
+        units
+    }
+
+    pub fn find(&mut self, unit: Unit, owner: PlayerNumber) -> Vec<UnitInfo> {
+        self.units()
+            .into_iter()
+            .filter(|info| info.unit == unit && info.owner == owner)
+            .collect()
+    }
+}
+
+/// Клиент без графики. Все полученные сообщения складываются в Inbox
+pub struct TestClient {
+    app: App,
+}
+impl TestClient {
+    fn new(port: u16) -> Self {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, QuinnetClientPlugin::default()))
+            .init_resource::<Inbox>()
+            .add_systems(Update, receive_messages);
+        app.finish();
+        app.cleanup();
+
+        app.world_mut()
+            .resource_mut::<QuinnetClient>()
+            .open_connection(
+                ClientEndpointConfiguration::from_ips(
+                    Ipv4Addr::LOCALHOST,
+                    port,
+                    LOCAL_BIND_IP,
+                    0,
+                ),
+                CertificateVerificationMode::SkipVerification,
+                ClientChannel::channels_config(),
+            )
+            .unwrap();
+        Self { app }
+    }
+
+    pub fn is_disconnected(&self) -> bool {
+        self.app
+            .world()
+            .resource::<QuinnetClient>()
+            .is_disconnected()
+    }
+
+    pub fn send(&mut self, message: ClientMessage) {
+        self.app
+            .world_mut()
+            .resource_mut::<QuinnetClient>()
+            .connection_mut()
+            .send_message_on(ClientChannel::OrderedReliable, message)
+            .unwrap();
+    }
+
+    pub fn received(&self) -> &[ServerMessage] {
+        &self.app.world().resource::<Inbox>().0
+    }
+
+    /// Номер игрока из StartGame
+    pub fn player(&self) -> Option<PlayerNumber> {
+        self.received().iter().find_map(|message| match message {
+            ServerMessage::StartGame(player) => Some(*player),
+            _ => None,
+        })
+    }
+
+    /// Сколько полученных сообщений подходит под условие
+    pub fn count(&self, matches: impl Fn(&ServerMessage) -> bool) -> usize {
+        self.received().iter().filter(|m| matches(m)).count()
+    }
+}
+
+#[derive(Resource, Default)]
+struct Inbox(Vec<ServerMessage>);
+
+fn receive_messages(mut client: ResMut<QuinnetClient>, mut inbox: ResMut<Inbox>) {
+    let Some(connection) = client.get_connection_mut() else {
+        return;
+    };
+    while let Some((_, message)) = connection.try_receive_message::<ServerMessage>() {
+        // Снимки приходят каждый тик, тестам нужны только те, что с контрольной суммой
+        if !matches!(message, ServerMessage::SyncEntities { checksum: None, .. }) {
+            inbox.0.push(message);
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use common::{PlayerNumber, ServerMessage, Unit};
+use server::ServerSettings;
+
+use crate::harness::{settings, TestMatch};
+
+#[test]
+fn two_clients_get_player_numbers() {
+    let mut game = TestMatch::new(settings(51001));
+    let first = game.join();
+    let second = game.join();
+    game.wait_until("оба получили StartGame", |m| {
+        m.clients.iter().all(|c| c.player().is_some())
+    });
+
+    assert_eq!(game.clients[first].player(), Some(PlayerNumber::One));
+    assert_eq!(game.clients[second].player(), Some(PlayerNumber::Two));
+    // Башни обоих игроков появляются у каждого клиента
+    game.wait_until("башни дошли до клиентов", |m| {
+        m.clients
+            .iter()
+            .all(|c| c.count(|m| matches!(m, ServerMessage::SpawnUnit { .. })) == 6)
+    });
+}
+
+#[test]
+fn third_client_is_turned_away() {
+    let mut game = TestMatch::new(settings(51002));
+    game.join();
+    game.join();
+    let third = game.connect();
+    game.wait_until("сервер отключил третьего", |m| {
+        m.clients[third].is_disconnected()
+    });
+
+    assert_eq!(game.lobby_len(), 2);
+    assert_eq!(game.clients[third].player(), None);
+}
+
+#[test]
+fn bot_takes_free_seat() {
+    let mut game = TestMatch::new(ServerSettings {
+        bot: Some(Default::default()),
+        bot_delay: 0.5,
+        ..settings(51003)
+    });
+    let human = game.join();
+    game.wait_until("бот занял место", |m| {
+        m.clients[human].player().is_some()
+    });
+    assert_eq!(game.clients[human].player(), Some(PlayerNumber::One));
+
+    // Рано или поздно бот что-нибудь разыгрывает
+    game.wait_until("бот разыграл карту", |m| {
+        m.units().iter().any(|u| {
+            u.owner == PlayerNumber::Two
+                && !matches!(u.unit, Unit::ArcherTower | Unit::KingTower)
+        })
+    });
+}
@@ -0,0 +1,6 @@
+//! Интеграционные тесты сервера: настоящие плагины, QUIC на localhost
+//! и клиенты без графики
+
+mod combat;
+mod harness;
+mod lobby;
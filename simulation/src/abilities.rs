@@ -0,0 +1,169 @@
+use bevy::prelude::*;
+use common::{ArenaPos, ChargeStats, DashStats, UnitState};
+
+use crate::{
+    ai::{Attack, AttackType, DeployTimer},
+    clock::TICK,
+    status_effects::{effects_rate, StatusEffects},
+    support::Damageable,
+    units::Origin,
+    SimSet,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        FixedUpdate,
+        (update_charges, update_dashes)
+            .chain()
+            .in_set(SimSet::Abilities),
+    );
+}
+
+/// Разгон копится, пока юнит движется. Сбрасывается остановкой, оглушением
+/// или заморозкой, а также первым ударом
+#[derive(Component)]
+pub struct Charge {
+    distance: f32,
+    pub speed: f32,
+    traveled: f32,
+    last_pos: Option<ArenaPos>,
+}
+impl From<&ChargeStats> for Charge {
+    fn from(stats: &ChargeStats) -> Self {
+        Self {
+            distance: stats.distance,
+            speed: stats.speed,
+            traveled: 0.,
+            last_pos: None,
+        }
+    }
+}
+impl Charge {
+    pub fn is_charged(&self) -> bool {
+        self.traveled >= self.distance
+    }
+    pub fn reset(&mut self) {
+        self.traveled = 0.;
+    }
+}
+
+fn update_charges(
+    mut query: Query<(
+        &mut Charge,
+        &mut UnitState,
+        &ArenaPos,
+        Option<&StatusEffects>,
+    )>,
+) {
+    for (mut charge, mut state, pos, effects) in &mut query {
+        let step = charge
+            .last_pos
+            .replace(*pos)
+            .map_or(0., |last| last.distance(pos));
+
+        match *state {
+            UnitState::Moving | UnitState::Charging if effects_rate(effects) > 0. => {
+                charge.traveled += step;
+                if charge.is_charged() {
+                    *state = UnitState::Charging;
+                }
+            }
+            // Разгон тратится на первый удар в update_attacks
+            UnitState::Attacking => {}
+            _ => {
+                charge.reset();
+                if let UnitState::Charging = *state {
+                    *state = UnitState::Moving;
+                }
+            }
+        }
+    }
+}
+
+/// Рывок доступен после перезарядки. Цель выбирается в update_unit_state
+#[derive(Component)]
+pub struct Dash {
+    min_range: f32,
+    max_range: f32,
+    speed: f32,
+    cooldown: Timer,
+    pub target: Option<Entity>,
+}
+impl From<&DashStats> for Dash {
+    fn from(stats: &DashStats) -> Self {
+        let mut cooldown = Timer::from_seconds(stats.cooldown, TimerMode::Once);
+        // Первый рывок доступен сразу
+        cooldown.tick(cooldown.duration());
+        Self {
+            min_range: stats.min_range,
+            max_range: stats.max_range,
+            speed: stats.speed,
+            cooldown,
+            target: None,
+        }
+    }
+}
+impl Dash {
+    pub fn max_range(&self) -> f32 {
+        self.max_range
+    }
+    pub fn can_reach(&self, distance: f32) -> bool {
+        self.cooldown.finished() && (self.min_range..=self.max_range).contains(&distance)
+    }
+}
+
+fn update_dashes(
+    mut dashers: Query<
+        (
+            Entity,
+            &mut Dash,
+            &mut UnitState,
+            &mut Attack,
+            Option<&StatusEffects>,
+            Option<&Origin>,
+        ),
+        Without<DeployTimer>,
+    >,
+    mut positions: Query<&mut ArenaPos>,
+    mut targets: Query<Damageable>,
+) {
+    for (entity, mut dash, mut state, mut attack, effects, origin) in &mut dashers {
+        let rate = effects_rate(effects);
+        let UnitState::Dashing = *state else {
+            dash.cooldown.tick(TICK.mul_f32(rate));
+            continue;
+        };
+
+        let target = dash.target.and_then(|target| {
+            let pos = *positions.get(target).ok()?;
+            Some((target, pos))
+        });
+        let Some((target, target_pos)) = target else {
+            // Цель погибла во время рывка
+            dash.target = None;
+            *state = UnitState::Moving;
+            continue;
+        };
+
+        let mut self_pos = positions.get_mut(entity).unwrap();
+        let step = dash.speed * rate * TICK.as_secs_f32();
+        let distance = self_pos.distance(&target_pos);
+        if distance > attack.range {
+            let direction = self_pos.direction(&target_pos);
+            *self_pos += direction.mul(step.min(distance));
+            continue;
+        }
+
+        // Приземление
+        if let (AttackType::Melee(damage), Ok(mut health)) =
+            (attack.a_type, targets.get_mut(target))
+        {
+            health.damage(damage, origin.map(|o| o.0));
+        }
+        dash.target = None;
+        dash.cooldown.reset();
+        attack.target = Some(target);
+        attack.cooldown_timer.reset();
+        *state = UnitState::Attacking;
+    }
+}
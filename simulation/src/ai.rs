@@ -0,0 +1,344 @@
+use bevy::prelude::*;
+use common::{ArenaPos, AttackStats, Health, Level, PlayerNumber, UnitState};
+pub use common::{AttackTargetType, AttackType};
+
+use crate::{
+    abilities::{Charge, Dash},
+    clock::TICK,
+    output::report_damage,
+    projectiles::SpawnProjectile,
+    spatial::SpatialIndex,
+    status_effects::{effects_rate, StatusEffects},
+    support::Damageable,
+    units::{Dormant, Origin, UnitDied, UnitType},
+    SimSet,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        FixedUpdate,
+        (
+            (
+                update_attacks,
+                update_unit_state,
+                update_deploy_timers,
+                update_movement,
+                update_decay,
+            )
+                .chain()
+                .in_set(SimSet::Ai),
+            (report_damage, check_health).chain().in_set(SimSet::Health),
+        ),
+    );
+}
+
+#[derive(Component)]
+pub struct Attack {
+    pub target: Option<Entity>,
+    pub a_type: AttackType,
+    pub t_type: AttackTargetType,
+    pub cooldown_timer: Timer,
+    pub range: f32,
+}
+impl Attack {
+    pub fn new(a_type: AttackType, targets: AttackTargetType, cd: f32, range: f32) -> Self {
+        Self {
+            target: None,
+            a_type,
+            t_type: targets,
+            cooldown_timer: Timer::from_seconds(cd, TimerMode::Repeating),
+            range,
+        }
+    }
+    pub fn from_stats(stats: &AttackStats, level: Level) -> Self {
+        let a_type = match stats.attack_type {
+            AttackType::Melee(damage) => AttackType::Melee(level.scale(damage)),
+            AttackType::Ranged(projectile, damage) => {
+                AttackType::Ranged(projectile, level.scale(damage))
+            }
+        };
+        Self::new(a_type, stats.targets, stats.cooldown, stats.range)
+    }
+}
+
+fn update_attacks(
+    mut attacks: Query<(
+        Entity,
+        &mut Attack,
+        Option<&StatusEffects>,
+        Option<&mut Charge>,
+        Option<&Origin>,
+    )>,
+    mut units: Query<(&ArenaPos, Damageable)>,
+    mut cmd: Commands,
+) {
+    for (attacker, mut attack, effects, mut charge, origin) in &mut attacks {
+        let origin = origin.map(|o| o.0);
+        // target есть только в UnitState::Attacking
+        let Some(receiver) = attack.target else {
+            attack.cooldown_timer.reset();
+            continue;
+        };
+        let Ok((_, mut health)) = units.get_mut(receiver) else {
+            // Все мертвы
+            attack.target = None;
+            continue;
+        };
+        let rate = effects_rate(effects);
+        let charged = rate > 0. && charge.as_ref().is_some_and(|c| c.is_charged());
+        if charged {
+            // После разгона первый удар наносится сразу
+            attack.cooldown_timer.reset();
+        } else if !attack
+            .cooldown_timer
+            .tick(TICK.mul_f32(rate))
+            .just_finished()
+        {
+            continue;
+        }
+
+        match attack.a_type {
+            AttackType::Melee(damage) if charged => {
+                health.damage(damage.saturating_mul(2), origin);
+                charge.as_mut().unwrap().reset();
+            }
+            AttackType::Melee(damage) => health.damage(damage, origin),
+            AttackType::Ranged(projectile, damage) => {
+                let (pos, _) = units.get(attacker).unwrap();
+                projectile.spawn(attacker, receiver, *pos, damage, &mut cmd)
+            }
+        }
+    }
+}
+
+fn check_health(query: Query<(Entity, &Health)>, mut cmd: Commands) {
+    // Эффекты гибели спавнят юнитов, порядок смертей должен быть стабильным
+    let mut dead: Vec<_> = query
+        .iter()
+        .filter(|(_, health)| health.0 == 0)
+        .map(|(entity, _)| entity)
+        .collect();
+    dead.sort_unstable();
+    for entity in dead {
+        cmd.trigger(UnitDied(entity));
+    }
+}
+
+#[derive(Component)]
+pub struct Movement {
+    pub target: Option<Entity>,
+    pub speed: f32,
+}
+impl Movement {
+    pub fn new(speed: f32) -> Self {
+        Self {
+            target: None,
+            speed,
+        }
+    }
+}
+fn update_movement(
+    mut query: Query<(Entity, &mut Movement, Option<&StatusEffects>), Without<DeployTimer>>,
+    states: Query<(&UnitState, Option<&Charge>)>,
+    mut positions: Query<&mut ArenaPos>,
+) {
+    for (entity, mut movement, effects) in &mut query {
+        let mut speed = movement.speed;
+        if let Ok((state, charge)) = states.get(entity) {
+            match (state, charge) {
+                (UnitState::Charging, Some(charge)) => speed = charge.speed,
+                (UnitState::Moving, _) => {}
+                _ => continue,
+            }
+        }
+        // target устанавливается в update_unit_state
+        let Some(target) = movement.target else {
+            continue;
+        };
+        let Ok(&target_pos) = positions.get(target) else {
+            movement.target = None;
+            continue;
+        };
+        let Ok(mut self_pos) = positions.get_mut(entity) else {
+            continue;
+        };
+        let direction = self_pos.direction(&target_pos);
+        let speed = speed * effects_rate(effects);
+        *self_pos += direction.mul(speed * TICK.as_secs_f32());
+    }
+}
+
+/// Юнит находится в UnitState::Deploying, пока таймер не истечёт
+#[derive(Component)]
+pub struct DeployTimer(pub Timer);
+impl DeployTimer {
+    pub fn new(deploy_time: f32) -> Self {
+        Self(Timer::from_seconds(deploy_time, TimerMode::Once))
+    }
+}
+fn update_deploy_timers(
+    mut query: Query<(Entity, &mut DeployTimer, &mut UnitState, Has<Movement>)>,
+    mut cmd: Commands,
+) {
+    for (entity, mut timer, mut state, has_movement) in &mut query {
+        if !timer.0.tick(TICK).just_finished() {
+            continue;
+        }
+        cmd.entity(entity).remove::<DeployTimer>();
+        *state = match has_movement {
+            true => UnitState::Moving,
+            false => UnitState::Idle,
+        };
+    }
+}
+
+#[derive(Component)]
+pub struct AggroRadius(pub f32);
+
+/// Постройки из карт теряют здоровье каждую секунду
+/// и разрушаются сами по истечении времени жизни
+#[derive(Component)]
+pub struct Decay {
+    timer: Timer,
+    amount: u16,
+}
+impl Decay {
+    pub fn new(max_health: u16, lifetime_secs: u16) -> Self {
+        Self {
+            timer: Timer::from_seconds(1., TimerMode::Repeating),
+            amount: max_health.div_ceil(lifetime_secs),
+        }
+    }
+}
+fn update_decay(mut query: Query<(&mut Decay, &mut Health)>) {
+    for (mut decay, mut health) in &mut query {
+        if decay.timer.tick(TICK).just_finished() {
+            health.0 = health.0.saturating_sub(decay.amount);
+        }
+    }
+}
+
+fn update_unit_state(
+    mut attackers: Query<
+        (
+            Entity,
+            &mut UnitState,
+            &mut Attack,
+            Option<&AggroRadius>,
+            Option<&mut Movement>,
+            Option<&mut Dash>,
+        ),
+        (Without<DeployTimer>, Without<Dormant>),
+    >,
+    receivers: Query<(Entity, &ArenaPos, &PlayerNumber, &UnitType)>,
+    card_buildings: Query<(), With<Decay>>,
+    index: Res<SpatialIndex>,
+) {
+    'outer: for (self_entity, mut state, mut attack, aggro_radius, mut movement, mut dash) in
+        &mut attackers
+    {
+        match *state {
+            // DeployTimer отфильтрован запросом
+            UnitState::Deploying => continue,
+            // Рывком управляет update_dashes
+            UnitState::Dashing => continue,
+            UnitState::Idle | UnitState::Moving | UnitState::Charging => {
+                let (_, self_pos, self_player_numer, self_type) =
+                    receivers.get(self_entity).unwrap();
+                let reach = [
+                    attack.range,
+                    aggro_radius.map_or(0., |a| a.0),
+                    dash.as_ref().map_or(0., |d| d.max_range()),
+                ]
+                .into_iter()
+                .fold(0., f32::max);
+
+                let nearby = index.units.nearby(*self_pos, reach);
+                for (entity, pos, player_number, unit_type) in receivers.iter_many(nearby) {
+                    if self_player_numer == player_number {
+                        // Своих не бьём
+                        continue;
+                    }
+                    match (attack.t_type, unit_type) {
+                        (AttackTargetType::Ground, UnitType::Air) => continue,
+                        (AttackTargetType::Buildings, UnitType::Air | UnitType::Ground) => {
+                            continue
+                        }
+                        _ => {}
+                    }
+
+                    if self_pos.distance(pos) <= attack.range {
+                        *state = UnitState::Attacking;
+                        attack.target = Some(entity);
+                        continue 'outer;
+                    }
+
+                    if let Some(dash) = dash.as_mut() {
+                        if dash.can_reach(self_pos.distance(pos)) {
+                            *state = UnitState::Dashing;
+                            dash.target = Some(entity);
+                            continue 'outer;
+                        }
+                    }
+
+                    // У всего что не является постройкой есть и AggroRadius и Movement
+                    if let (Some(aggro_radius), Some(movement)) =
+                        (aggro_radius, movement.as_mut())
+                    {
+                        if self_pos.distance(pos) <= aggro_radius.0 {
+                            movement.target = Some(entity);
+                            continue 'outer;
+                        }
+                    }
+                }
+
+                // Если никого нет вблизи, двигаемся к ближайшей постройке.
+                // Постройки из карт притягивают только наземных, летающие идут к башням
+                let Some(movement) = movement.as_mut() else {
+                    continue;
+                };
+                let mut nearest_building = None;
+                let mut minimal_distance = 1000.;
+                for (entity, pos, player_number, _) in receivers.iter_many(&index.buildings) {
+                    let distance = self_pos.distance(pos);
+                    if self_player_numer == player_number || distance > minimal_distance {
+                        continue;
+                    }
+                    if *self_type != UnitType::Ground && card_buildings.contains(entity) {
+                        continue;
+                    }
+
+                    minimal_distance = distance;
+                    nearest_building = Some(entity);
+                }
+                let Some(nearest_building) = nearest_building else {
+                    // Башен врага не осталось, игра должна закончиться
+                    continue;
+                };
+                movement.target = Some(nearest_building);
+            }
+            UnitState::Attacking => {
+                if let Some(target) = attack.target {
+                    let (_, self_pos, _, _) = receivers.get(self_entity).unwrap();
+
+                    if let Ok((_, pos, _, _)) = receivers.get(target) {
+                        if self_pos.distance(pos) > attack.range {
+                            match movement.as_mut() {
+                                Some(_) => *state = UnitState::Moving,
+                                None => *state = UnitState::Idle,
+                            }
+                        }
+                        continue;
+                    };
+
+                    continue;
+                }
+
+                match movement.as_mut() {
+                    Some(_) => *state = UnitState::Moving,
+                    None => *state = UnitState::Idle,
+                }
+            }
+        }
+    }
+}
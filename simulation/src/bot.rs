@@ -0,0 +1,406 @@
+//! Противник без клиента. Решает, какую карту и куда разыграть, по тому же
+//! состоянию симуляции, а разыгрывает её вызывающий, как карту игрока
+
+use std::str::FromStr;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use common::{
+    ArenaPos, AttackTargetType, AttackType, Card, CardsData, Health, PlayerNumber, Spell,
+    Unit, UnitType, UnitsData, ARENA_HALF_WIDTH, MAX_ELIXIR,
+};
+
+use crate::{clock::Tick, elixir::Elixir, SimRng, TICK};
+
+/// Сколько карт из колоды на руке, остальные ждут своей очереди
+const HAND: usize = 4;
+/// Враги ближе этого к самому продвинутому считаются одной атакой
+const CLUSTER_RADIUS: f32 = 3.5;
+/// Сколько секунд после Гиганта бот копит эликсир на поддержку
+const PUSH_SUPPORT_TIME: f32 = 10.;
+
+/// Колода бота, когда другой не задана
+pub const BOT_DECK: [Card; 8] = [
+    Card::Giant,
+    Card::Musketeer,
+    Card::Bomber,
+    Card::Rus,
+    Card::Bats,
+    Card::Zap,
+    Card::Fireball,
+    Card::BatHorde,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Difficulty {
+    // Разыгрывает случайные карты, как только хватает эликсира
+    Easy,
+    #[default]
+    Normal,
+    // Быстрее реагирует, защищается дешёвыми картами и копит на полный пуш
+    Hard,
+}
+impl Difficulty {
+    /// Секунды между решениями, то есть задержка реакции на угрозу
+    fn reaction(self) -> f32 {
+        match self {
+            Difficulty::Easy => 2.,
+            Difficulty::Normal => 1.,
+            Difficulty::Hard => 0.4,
+        }
+    }
+
+    /// Эликсир, с которого бот начинает атаку без угрозы на своей половине
+    fn push_elixir(self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.,
+            Difficulty::Normal => 7.,
+            Difficulty::Hard => 9.,
+        }
+    }
+}
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "easy" => Ok(Difficulty::Easy),
+            "normal" => Ok(Difficulty::Normal),
+            "hard" => Ok(Difficulty::Hard),
+            _ => Err(format!("Неизвестная сложность бота: {s}")),
+        }
+    }
+}
+
+/// Всё, что бот видит на арене
+#[derive(SystemParam)]
+pub struct Battlefield<'w, 's> {
+    units: Query<
+        'w,
+        's,
+        (
+            &'static Unit,
+            &'static ArenaPos,
+            &'static PlayerNumber,
+            &'static UnitType,
+            &'static Health,
+        ),
+    >,
+    units_data: Res<'w, UnitsData>,
+    cards: Res<'w, CardsData>,
+    elixir: Res<'w, Elixir>,
+    tick: Res<'w, Tick>,
+}
+
+/// Юнит с точки зрения бота. Координаты повёрнуты так, будто бот
+/// играет первым: своя половина внизу, y < 0
+struct Seen {
+    unit: Unit,
+    pos: ArenaPos,
+    ally: bool,
+    unit_type: UnitType,
+    health: u16,
+}
+
+#[derive(Clone, Debug)]
+pub struct Bot {
+    player: PlayerNumber,
+    difficulty: Difficulty,
+    // Первые HAND карт на руке, сыгранная уходит в конец, как у клиента
+    deck: Vec<Card>,
+    next_decision: u64,
+    // До какого тика бот поддерживает своего Гиганта
+    push_until: Option<u64>,
+    rng: SimRng,
+}
+impl Bot {
+    pub fn new(
+        player: PlayerNumber,
+        deck: Vec<Card>,
+        difficulty: Difficulty,
+        seed: u64,
+    ) -> Self {
+        Self {
+            player,
+            difficulty,
+            deck,
+            next_decision: 0,
+            push_until: None,
+            rng: SimRng::new(seed),
+        }
+    }
+
+    pub fn player(&self) -> PlayerNumber {
+        self.player
+    }
+
+    pub fn deck(&self) -> &[Card] {
+        &self.deck
+    }
+
+    /// Вызывается хотя бы раз за тик. Возвращает карту и точку розыгрыша
+    /// в координатах арены. Эликсир спишет симуляция, когда применит розыгрыш
+    pub fn think(&mut self, field: &Battlefield) -> Option<(Card, ArenaPos)> {
+        let tick = field.tick.0;
+
+        if tick < self.next_decision {
+            return None;
+        }
+        self.next_decision = tick + (self.difficulty.reaction() / TICK.as_secs_f32()) as u64;
+        if self.push_until.is_some_and(|until| tick >= until) {
+            self.push_until = None;
+        }
+
+        let seen = self.look(field);
+        let (index, pos) = self
+            .defend(field, &seen)
+            .or_else(|| self.support(field, &seen))
+            .or_else(|| self.attack(field, &seen))?;
+
+        let card = self.deck[index];
+        if card == Card::Giant {
+            self.push_until = Some(tick + (PUSH_SUPPORT_TIME / TICK.as_secs_f32()) as u64);
+        }
+        self.cycle(index);
+        Some((card, self.flip(snap(card, pos))))
+    }
+
+    fn elixir(&self, field: &Battlefield) -> f32 {
+        field.elixir.amount(self.player)
+    }
+
+    /// Второй игрок видит арену перевёрнутой, преобразование обратно то же самое
+    fn flip(&self, pos: ArenaPos) -> ArenaPos {
+        match self.player {
+            PlayerNumber::One => pos,
+            PlayerNumber::Two => ArenaPos(-pos.0, -pos.1),
+        }
+    }
+
+    fn look(&self, field: &Battlefield) -> Vec<Seen> {
+        field
+            .units
+            .iter()
+            .map(|(&unit, &pos, &owner, &unit_type, health)| Seen {
+                unit,
+                pos: self.flip(pos),
+                ally: owner == self.player,
+                unit_type,
+                health: health.0,
+            })
+            .collect()
+    }
+
+    fn cycle(&mut self, index: usize) {
+        if self.deck.len() <= HAND {
+            return;
+        }
+        let card = self.deck[index];
+        self.deck[index] = self.deck.remove(HAND);
+        self.deck.push(card);
+    }
+
+    /// Карты на руке, на которые хватает эликсира
+    fn affordable<'a>(&'a self, field: &'a Battlefield) -> impl Iterator<Item = usize> + 'a {
+        (0..self.deck.len().min(HAND)).filter(|&i| {
+            let cost = field.cards.stats(self.deck[i]).cost as u32;
+            field.elixir.can_afford(self.player, cost)
+        })
+    }
+
+    fn pick(&mut self, field: &Battlefield, candidates: Vec<usize>) -> Option<usize> {
+        match self.difficulty {
+            Difficulty::Easy if !candidates.is_empty() => {
+                Some(candidates[self.rng.below(candidates.len() as u32) as usize])
+            }
+            Difficulty::Hard => candidates
+                .into_iter()
+                .min_by_key(|&i| field.cards.stats(self.deck[i]).cost),
+            _ => candidates.first().copied(),
+        }
+    }
+
+    /// Вражеские юниты на своей половине. Отвечает на самый продвинутый
+    fn defend(&mut self, field: &Battlefield, seen: &[Seen]) -> Option<(usize, ArenaPos)> {
+        let lead = seen
+            .iter()
+            .filter(|s| !s.ally && s.unit_type != UnitType::Building && s.pos.1 < 0.)
+            .min_by(|a, b| a.pos.1.total_cmp(&b.pos.1))?;
+        let cluster: Vec<&Seen> = seen
+            .iter()
+            .filter(|s| !s.ally && s.unit_type != UnitType::Building)
+            .filter(|s| s.pos.distance(&lead.pos) <= CLUSTER_RADIUS)
+            .collect();
+        let air = cluster.iter().any(|s| s.unit_type == UnitType::Air);
+        let health: u32 = cluster.iter().map(|s| s.health as u32).sum();
+        let center = ArenaPos(
+            cluster.iter().map(|s| s.pos.0).sum::<f32>() / cluster.len() as f32,
+            cluster.iter().map(|s| s.pos.1).sum::<f32>() / cluster.len() as f32,
+        );
+
+        // С мелочью справятся башни, эликсир лучше сохранить
+        if self.difficulty == Difficulty::Hard && health < 150 {
+            return None;
+        }
+
+        let candidates = self
+            .affordable(field)
+            .filter(|&i| {
+                let card = self.deck[i];
+                match (card.spell(), self.difficulty) {
+                    (Some(spell), Difficulty::Easy) => {
+                        damage_spell(spell) && cluster.len() > 1
+                    }
+                    (Some(spell), _) => damage_spell(spell) && (cluster.len() > 1 || air),
+                    (None, Difficulty::Easy) => true,
+                    (None, _) if air => hits_air(field, card),
+                    (None, _) => true,
+                }
+            })
+            .collect();
+        let index = self.pick(field, candidates)?;
+
+        let card = self.deck[index];
+        let pos = if card.spell().is_some() {
+            center
+        } else if is_building(field, card) {
+            // Постройка в центре притягивает наземных юнитов с обеих линий
+            ArenaPos(lead.pos.0.clamp(-2., 2.), -5.5)
+        } else {
+            // Между угрозой и своими башнями
+            ArenaPos(lead.pos.0, lead.pos.1 - 3.)
+        };
+        Some((index, pos))
+    }
+
+    /// Поддержка за своим Гигантом: предпочтительно юниты с дальней атакой
+    fn support(&mut self, field: &Battlefield, seen: &[Seen]) -> Option<(usize, ArenaPos)> {
+        self.push_until?;
+        let giant = seen
+            .iter()
+            .filter(|s| s.ally && s.unit == Unit::Giant)
+            .max_by(|a, b| a.pos.1.total_cmp(&b.pos.1))?;
+
+        let mut candidates: Vec<usize> = self
+            .affordable(field)
+            .filter(|&i| {
+                let card = self.deck[i];
+                card.spell().is_none() && card != Card::Giant && !is_building(field, card)
+            })
+            .collect();
+        candidates.sort_by_key(|&i| !ranged(field, self.deck[i]));
+        let index = candidates.first().copied()?;
+
+        self.push_until = None;
+        Some((index, ArenaPos(giant.pos.0, giant.pos.1 - 2.)))
+    }
+
+    /// Атака по линии с более слабой башней противника
+    fn attack(&mut self, field: &Battlefield, seen: &[Seen]) -> Option<(usize, ArenaPos)> {
+        let elixir = self.elixir(field);
+        let leaking = elixir >= MAX_ELIXIR as f32;
+        if elixir < self.difficulty.push_elixir() && !leaking {
+            return None;
+        }
+        // Пока бот копит на поддержку Гиганта, тратиться на другое незачем
+        if self.push_until.is_some() && !leaking {
+            return None;
+        }
+
+        let target = seen
+            .iter()
+            .filter(|s| !s.ally && s.unit_type == UnitType::Building && s.pos.1 > 0.)
+            .min_by_key(|s| (s.unit == Unit::KingTower, s.health))
+            .map(|s| s.pos)
+            .unwrap_or(ArenaPos(0., 13.));
+        let lane = match target.0 {
+            x if x.abs() > 1. => x,
+            _ => [-5.5, 5.5][self.rng.below(2) as usize],
+        };
+
+        let index = if self.difficulty == Difficulty::Easy {
+            let candidates = self.affordable(field).collect();
+            self.pick(field, candidates)?
+        } else {
+            let affordable: Vec<usize> = self.affordable(field).collect();
+            let giant = affordable.iter().find(|&&i| self.deck[i] == Card::Giant);
+            let unit = affordable.iter().find(|&&i| {
+                let card = self.deck[i];
+                card.spell().is_none() && !is_building(field, card)
+            });
+            match (giant, unit) {
+                (Some(&giant), _) => giant,
+                (None, Some(&unit)) => unit,
+                // Остались только заклинания и постройки, сбросить самую дешёвую
+                (None, None) if leaking => affordable
+                    .into_iter()
+                    .min_by_key(|&i| field.cards.stats(self.deck[i]).cost)?,
+                (None, None) => return None,
+            }
+        };
+
+        let card = self.deck[index];
+        let pos = match card.spell() {
+            Some(Spell::Rage) => seen
+                .iter()
+                .filter(|s| s.ally && s.unit_type != UnitType::Building)
+                .max_by(|a, b| a.pos.1.total_cmp(&b.pos.1))
+                .map_or(ArenaPos(lane, -2.5), |s| s.pos),
+            Some(_) => target,
+            None if is_building(field, card) => ArenaPos(0., -5.5),
+            // Гигант идёт из глубины, пока копится эликсир на поддержку
+            None if card == Card::Giant => ArenaPos(lane, -10.5),
+            None => ArenaPos(lane, -2.5),
+        };
+        Some((index, pos))
+    }
+}
+
+fn damage_spell(spell: Spell) -> bool {
+    matches!(spell, Spell::Fireball | Spell::Arrows | Spell::Zap)
+}
+
+fn card_units<'a>(field: &'a Battlefield, card: Card) -> impl Iterator<Item = Unit> + 'a {
+    field
+        .cards
+        .stats(card)
+        .formation
+        .iter()
+        .map(|&(unit, _)| unit)
+}
+
+fn hits_air(field: &Battlefield, card: Card) -> bool {
+    card_units(field, card).any(|unit| {
+        field
+            .units_data
+            .stats(unit)
+            .attack
+            .as_ref()
+            .is_some_and(|attack| attack.targets == AttackTargetType::All)
+    })
+}
+
+fn is_building(field: &Battlefield, card: Card) -> bool {
+    card_units(field, card)
+        .any(|unit| field.units_data.stats(unit).unit_type == UnitType::Building)
+}
+
+fn ranged(field: &Battlefield, card: Card) -> bool {
+    card_units(field, card).any(|unit| {
+        field
+            .units_data
+            .stats(unit)
+            .attack
+            .as_ref()
+            .is_some_and(|attack| matches!(attack.attack_type, AttackType::Ranged(..)))
+    })
+}
+
+/// Центр клетки, юниты только на своей половине, как при розыгрыше с клиента
+fn snap(card: Card, ArenaPos(x, y): ArenaPos) -> ArenaPos {
+    let x = x.clamp(-ARENA_HALF_WIDTH, ARENA_HALF_WIDTH - 1.).floor() + 0.5;
+    let y = match card.spell() {
+        Some(_) => y.clamp(-16., 15.).floor() + 0.5,
+        None => y.floor().clamp(-16., -2.) + 0.5,
+    };
+    ArenaPos(x, y)
+}
@@ -0,0 +1,24 @@
+//! Симуляция не смотрит на реальное время: каждый тик все таймеры
+//! сдвигаются ровно на TICK, поэтому результат зависит только от числа тиков
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(Time::<Fixed>::from_duration(TICK));
+    app.init_resource::<Tick>();
+
+    app.add_systems(FixedFirst, advance_tick);
+}
+
+/// 64 тика в секунду, как у FixedUpdate по умолчанию
+pub const TICK: Duration = Duration::from_micros(15_625);
+
+/// Номер текущего шага FixedUpdate с начала матча
+#[derive(Resource, Default, Deref, Clone, Copy, Debug)]
+pub struct Tick(pub u64);
+
+fn advance_tick(mut tick: ResMut<Tick>) {
+    tick.0 += 1;
+}
@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+use common::{PlayerNumber, ELIXIR_INTERVAL, MAX_ELIXIR};
+
+use crate::clock::TICK;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Elixir>();
+    app.add_systems(FixedFirst, regenerate_elixir);
+}
+
+/// Сколько тиков копится единица эликсира
+fn elixir_ticks() -> u32 {
+    (ELIXIR_INTERVAL / TICK.as_secs_f32()).round() as u32
+}
+
+/// Эликсир игроков в тиках накопления, чтобы за матч не набегала
+/// ошибка округления. Списывается, когда розыгрыш применяется
+#[derive(Resource, Default, Clone, Copy, Debug)]
+pub struct Elixir([u32; 2]);
+impl Elixir {
+    pub fn amount(&self, player: PlayerNumber) -> f32 {
+        self.0[player as usize] as f32 / elixir_ticks() as f32
+    }
+
+    pub fn can_afford(&self, player: PlayerNumber, cost: u32) -> bool {
+        self.0[player as usize] >= cost * elixir_ticks()
+    }
+
+    pub(crate) fn spend(&mut self, player: PlayerNumber, cost: u8) {
+        let stored = &mut self.0[player as usize];
+        *stored = stored.saturating_sub(cost as u32 * elixir_ticks());
+    }
+
+    /// Эликсир за уничтоженных юнитов, см. SimEvent::ElixirAwarded
+    pub(crate) fn add(&mut self, player: PlayerNumber, amount: u8) {
+        let stored = &mut self.0[player as usize];
+        *stored = (*stored + amount as u32 * elixir_ticks()).min(max_elixir());
+    }
+}
+
+fn max_elixir() -> u32 {
+    MAX_ELIXIR as u32 * elixir_ticks()
+}
+
+fn regenerate_elixir(mut elixir: ResMut<Elixir>) {
+    for stored in &mut elixir.0 {
+        *stored = (*stored + 1).min(max_elixir());
+    }
+}
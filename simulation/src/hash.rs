@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+use common::{ArenaPos, Fnv, Health, PlayerNumber, Shield, Unit, UnitState};
+
+use crate::{clock::Tick, elixir::Elixir};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<StateHash>();
+    app.add_systems(FixedLast, update_state_hash);
+}
+
+/// Хеш состояния матча на конце тика. Два прогона одних и тех же
+/// розыгрышей с одним seed должны давать одинаковые хеши на каждом тике
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct StateHash {
+    pub tick: u64,
+    pub hash: u64,
+}
+
+fn update_state_hash(
+    units: Query<(
+        Entity,
+        &Unit,
+        &ArenaPos,
+        &UnitState,
+        &Health,
+        Option<&Shield>,
+    )>,
+    projectiles: Query<(Entity, &ArenaPos), Without<Unit>>,
+    tick: Res<Tick>,
+    elixir: Res<Elixir>,
+    mut state_hash: ResMut<StateHash>,
+) {
+    let mut units: Vec<_> = units.iter().collect();
+    units.sort_unstable_by_key(|(entity, ..)| *entity);
+    let mut projectiles: Vec<_> = projectiles.iter().collect();
+    projectiles.sort_unstable_by_key(|(entity, _)| *entity);
+
+    let mut fnv = Fnv::new();
+    fnv.write(&tick.0.to_le_bytes());
+    for player in [PlayerNumber::One, PlayerNumber::Two] {
+        fnv.write(&elixir.amount(player).to_bits().to_le_bytes());
+    }
+    for (entity, unit, pos, state, health, shield) in units {
+        fnv.write(&entity.to_bits().to_le_bytes());
+        fnv.write(&[*unit as u8, *state as u8]);
+        fnv.write(&pos.0.to_bits().to_le_bytes());
+        fnv.write(&pos.1.to_bits().to_le_bytes());
+        fnv.write(&health.0.to_le_bytes());
+        fnv.write(&shield.map_or(0, |s| s.0).to_le_bytes());
+    }
+    for (entity, pos) in projectiles {
+        fnv.write(&entity.to_bits().to_le_bytes());
+        fnv.write(&pos.0.to_bits().to_le_bytes());
+        fnv.write(&pos.1.to_bits().to_le_bytes());
+    }
+
+    *state_hash = StateHash {
+        tick: tick.0,
+        hash: fnv.finish(),
+    };
+}
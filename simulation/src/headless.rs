@@ -0,0 +1,141 @@
+use bevy::{app::FixedMain, ecs::system::SystemState, prelude::*};
+use common::{
+    ArenaPos, Card, CardsData, Level, PlayerNumber, Replay, ServerMessage, Unit, UnitsData,
+};
+
+use crate::{
+    check_play, plugin, protocol::Snapshot, units::SpawnUnitEvent, Battlefield, Bot, CardPlay,
+    Elixir, PendingPlays, SimEvent, SimRng, StartMatch, StateHash, Tick,
+};
+
+/// Матч в собственном World без цикла App. Тики идут только по вызову step,
+/// поэтому матч можно прокручивать быстрее реального времени.
+/// Нужен для просмотра записей, самоигры и тестов
+pub struct HeadlessMatch {
+    world: World,
+    snapshot: SystemState<Snapshot<'static, 'static>>,
+    battlefield: SystemState<Battlefield<'static, 'static>>,
+}
+impl HeadlessMatch {
+    pub fn new(units: UnitsData, cards: CardsData, seed: u64) -> Self {
+        let mut sim = Self::empty(units, cards, seed);
+        sim.world.trigger(StartMatch);
+        sim.world.flush();
+        sim
+    }
+
+    /// Арена без башен. Юнитов расставляет вызывающий через spawn
+    pub fn empty(units: UnitsData, cards: CardsData, seed: u64) -> Self {
+        let mut app = App::new();
+        app.add_plugins(plugin);
+        app.insert_resource(units);
+        app.insert_resource(cards);
+        app.insert_resource(SimRng::new(seed));
+        app.finish();
+        app.cleanup();
+
+        let mut world = std::mem::take(app.world_mut());
+        let snapshot = SystemState::new(&mut world);
+        let battlefield = SystemState::new(&mut world);
+        Self {
+            world,
+            snapshot,
+            battlefield,
+        }
+    }
+
+    /// Матч с розыгрышами из записи. Уровни карт берутся из колод в заголовке
+    pub fn from_replay(replay: &Replay, units: UnitsData, cards: CardsData) -> Self {
+        let mut sim = Self::new(units, cards, replay.header.seed);
+        for input in &replay.inputs {
+            sim.play(CardPlay {
+                tick: input.tick,
+                player: input.player,
+                card: input.card,
+                level: replay.level(input.player, input.card),
+                placement: input.placement,
+            });
+        }
+        sim
+    }
+
+    /// Ставит юнита в обход карт и колод. Событие UnitSpawned приходит как обычно
+    pub fn spawn(
+        &mut self,
+        unit: Unit,
+        pos: ArenaPos,
+        owner: PlayerNumber,
+        level: Level,
+    ) -> Entity {
+        self.world
+            .trigger(SpawnUnitEvent(unit, pos, owner, level, None));
+        self.world.flush();
+        self.world
+            .resource::<Events<SimEvent>>()
+            .iter_current_update_events()
+            .filter_map(|event| match event {
+                SimEvent::UnitSpawned { entity, .. } => Some(*entity),
+                _ => None,
+            })
+            .last()
+            .unwrap()
+    }
+
+    /// Та же проверка эликсира и места, что у розыгрышей на сервере
+    pub fn check(&self, play: &CardPlay) -> Result<(), String> {
+        check_play(
+            play,
+            self.world.resource::<Elixir>(),
+            self.world.resource::<PendingPlays>(),
+            self.world.resource::<CardsData>(),
+        )
+    }
+
+    pub fn play(&mut self, play: CardPlay) {
+        self.world.resource_mut::<PendingPlays>().push(play);
+    }
+
+    /// Один тик симуляции и все события, случившиеся с прошлого вызова
+    pub fn step(&mut self) -> Vec<SimEvent> {
+        self.world.run_schedule(FixedMain);
+        self.events()
+    }
+
+    /// События, накопившиеся вне step, например башни из StartMatch
+    pub fn events(&mut self) -> Vec<SimEvent> {
+        self.world
+            .resource_mut::<Events<SimEvent>>()
+            .drain()
+            .collect()
+    }
+
+    pub fn tick(&self) -> u64 {
+        self.world.resource::<Tick>().0
+    }
+
+    pub fn elixir(&self, player: PlayerNumber) -> f32 {
+        self.world.resource::<Elixir>().amount(player)
+    }
+
+    pub fn state_hash(&self) -> StateHash {
+        *self.world.resource::<StateHash>()
+    }
+
+    /// То же сообщение, что сервер рассылает клиентам после каждого тика
+    pub fn snapshot(&mut self) -> ServerMessage {
+        self.snapshot.get(&self.world).message()
+    }
+
+    /// Решение бота по текущему состоянию матча. Розыгрыш добавляет вызывающий
+    pub fn think(&mut self, bot: &mut Bot) -> Option<(Card, ArenaPos)> {
+        bot.think(&self.battlefield.get(&self.world))
+    }
+
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+}
@@ -0,0 +1,120 @@
+use bevy::prelude::*;
+use common::{
+    ArenaPos, Card, CardsData, Level, PlayerNumber, Unit, ARENA_HALF_HEIGHT, ARENA_HALF_WIDTH,
+    RIVER_HALF_WIDTH,
+};
+
+use crate::{clock::Tick, elixir::Elixir, spells::SpawnSpell, units::SpawnUnit};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<PendingPlays>();
+    app.add_observer(start_match);
+    app.add_systems(FixedPreUpdate, apply_card_plays);
+}
+
+/// Карта, разыгранная игроком. Уровень уже проверен вызывающим
+#[derive(Clone, Copy, Debug)]
+pub struct CardPlay {
+    pub tick: u64,
+    pub player: PlayerNumber,
+    pub card: Card,
+    pub level: Level,
+    pub placement: ArenaPos,
+}
+
+/// Розыгрыши, ожидающие своего тика. Применяются в порядке добавления
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct PendingPlays(Vec<CardPlay>);
+
+/// Проверка розыгрыша до того, как он попадёт в PendingPlays.
+/// Эликсир за ожидающие розыгрыши ещё не списан, поэтому учитывается здесь
+pub fn check_play(
+    play: &CardPlay,
+    elixir: &Elixir,
+    pending: &PendingPlays,
+    cards: &CardsData,
+) -> Result<(), String> {
+    let reserved: u32 = pending
+        .iter()
+        .filter(|p| p.player == play.player)
+        .map(|p| cards.stats(p.card).cost as u32)
+        .sum();
+    let cost = cards.stats(play.card).cost as u32;
+    if !elixir.can_afford(play.player, reserved + cost) {
+        return Err(format!(
+            "не хватает эликсира на {:?}: {:.1} из {cost}",
+            play.card,
+            elixir.amount(play.player) - reserved as f32
+        ));
+    }
+    if !placement_allowed(play.card, play.player, play.placement) {
+        return Err(format!(
+            "{:?} нельзя разыграть в ({:.1}, {:.1})",
+            play.card, play.placement.0, play.placement.1
+        ));
+    }
+    Ok(())
+}
+
+/// Юнитов можно ставить только на своей половине до реки, заклинания - куда угодно
+fn placement_allowed(card: Card, player: PlayerNumber, ArenaPos(x, y): ArenaPos) -> bool {
+    // Своя половина второго игрока сверху
+    let y = match player {
+        PlayerNumber::One => y,
+        PlayerNumber::Two => -y,
+    };
+    let max_y = match card.spell() {
+        Some(_) => ARENA_HALF_HEIGHT,
+        None => -RIVER_HALF_WIDTH,
+    };
+    x.abs() <= ARENA_HALF_WIDTH && (-ARENA_HALF_HEIGHT..=max_y).contains(&y)
+}
+
+fn apply_card_plays(
+    mut pending: ResMut<PendingPlays>,
+    mut elixir: ResMut<Elixir>,
+    tick: Res<Tick>,
+    cards: Res<CardsData>,
+    mut cmd: Commands,
+) {
+    pending.retain(|play| {
+        if play.tick > tick.0 {
+            return true;
+        }
+        elixir.spend(play.player, cards.stats(play.card).cost);
+        if let Some(spell) = play.card.spell() {
+            spell.spawn(play.placement, play.player, play.level, &mut cmd);
+            return false;
+        }
+        for (unit, offset) in cards.stats(play.card).formation_for(play.player) {
+            let mut pos = play.placement;
+            pos += offset;
+            unit.spawn(pos, play.player, play.level, Some(play.card), &mut cmd);
+        }
+        false
+    });
+}
+
+/// Ставит башни обоих игроков и начинает отсчёт тиков заново
+#[derive(Event)]
+pub struct StartMatch;
+
+fn start_match(
+    _trigger: Trigger<StartMatch>,
+    mut tick: ResMut<Tick>,
+    mut elixir: ResMut<Elixir>,
+    mut cmd: Commands,
+) {
+    use PlayerNumber::*;
+
+    *tick = Tick(0);
+    *elixir = Elixir::default();
+
+    Unit::ArcherTower.spawn(ArenaPos(-5.5, -9.5), One, Level::default(), None, &mut cmd);
+    Unit::KingTower.spawn(ArenaPos(0., -13.), One, Level::default(), None, &mut cmd);
+    Unit::ArcherTower.spawn(ArenaPos(5.5, -9.5), One, Level::default(), None, &mut cmd);
+
+    Unit::ArcherTower.spawn(ArenaPos(-5.5, 9.5), Two, Level::default(), None, &mut cmd);
+    Unit::KingTower.spawn(ArenaPos(0., 13.), Two, Level::default(), None, &mut cmd);
+    Unit::ArcherTower.spawn(ArenaPos(5.5, 9.5), Two, Level::default(), None, &mut cmd);
+}
@@ -0,0 +1,99 @@
+use bevy::prelude::*;
+use common::{ArenaPos, PlayerNumber, ARENA_HALF_HEIGHT, ARENA_HALF_WIDTH, RIVER_HALF_WIDTH};
+
+use crate::{
+    abilities::Charge,
+    ai::Attack,
+    spatial::SpatialIndex,
+    units::{Hitbox, UnitType},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(apply_knockback);
+}
+
+#[derive(Component)]
+pub struct Mass(pub f32);
+
+/// Сила, с которой снаряды юнита отбрасывают от точки попадания
+#[derive(Component, Clone, Copy)]
+pub struct KnockbackForce(pub f32);
+
+/// Отбрасывание юнитов игрока side от точки удара.
+/// Юнит массы 1 отлетает на force клеток
+#[derive(Event)]
+pub struct Knockback {
+    pub center: ArenaPos,
+    pub radius: f32,
+    pub force: f32,
+    pub side: PlayerNumber,
+    // Бомбы не задевают летающих
+    pub ground_only: bool,
+}
+
+fn apply_knockback(
+    trigger: Trigger<Knockback>,
+    mut units: Query<(
+        &mut ArenaPos,
+        &PlayerNumber,
+        &UnitType,
+        &Hitbox,
+        &Mass,
+        Option<&mut Attack>,
+        Option<&mut Charge>,
+    )>,
+    index: Res<SpatialIndex>,
+) {
+    let knockback = trigger.event();
+    let mut targets =
+        units.iter_many_mut(index.units.nearby(knockback.center, knockback.radius));
+    while let Some((mut pos, player_num, unit_type, hitbox, mass, attack, charge)) =
+        targets.fetch_next()
+    {
+        // Постройки не сдвинуть
+        if *player_num != knockback.side || *unit_type == UnitType::Building {
+            continue;
+        }
+        if knockback.ground_only && *unit_type == UnitType::Air {
+            continue;
+        }
+        if knockback.center.distance(&pos) > knockback.radius + hitbox.0 {
+            continue;
+        }
+
+        let mut direction = knockback.center.direction(&pos);
+        if direction == ArenaPos(0., 0.) {
+            // Удар точно в юнита отбрасывает его к своей стороне
+            direction = match player_num {
+                PlayerNumber::One => ArenaPos(0., -1.),
+                PlayerNumber::Two => ArenaPos(0., 1.),
+            };
+        }
+        let mut pushed = *pos;
+        pushed += direction.mul(knockback.force / mass.0);
+        *pos = clamp_pushed(*pos, pushed, *unit_type);
+
+        // Толчок сбивает атаку и разгон
+        if let Some(mut attack) = attack {
+            attack.cooldown_timer.reset();
+        }
+        if let Some(mut charge) = charge {
+            charge.reset();
+        }
+    }
+}
+
+/// Толчок не выносит юнита за пределы арены, а наземного ещё и через реку.
+/// Юнит на мосту сдвигается только вдоль моста и не дальше берегов
+fn clamp_pushed(from: ArenaPos, to: ArenaPos, unit_type: UnitType) -> ArenaPos {
+    let x = to.0.clamp(-ARENA_HALF_WIDTH, ARENA_HALF_WIDTH);
+    let y = to.1.clamp(-ARENA_HALF_HEIGHT, ARENA_HALF_HEIGHT);
+    if unit_type != UnitType::Ground {
+        return ArenaPos(x, y);
+    }
+    match from.1 {
+        y0 if y0 <= -RIVER_HALF_WIDTH => ArenaPos(x, y.min(-RIVER_HALF_WIDTH)),
+        y0 if y0 >= RIVER_HALF_WIDTH => ArenaPos(x, y.max(RIVER_HALF_WIDTH)),
+        _ => ArenaPos(from.0, y.clamp(-RIVER_HALF_WIDTH, RIVER_HALF_WIDTH)),
+    }
+}
@@ -0,0 +1,86 @@
+//! Правила боя без сети и графики.
+//! Матч идёт в обычном App с MinimalPlugins: на вход подаются розыгрыши карт
+//! с номером тика, на выходе получается поток SimEvent
+
+use bevy::prelude::*;
+
+mod abilities;
+mod ai;
+mod bot;
+mod clock;
+mod elixir;
+mod hash;
+mod headless;
+mod input;
+mod knockback;
+mod output;
+mod projectiles;
+mod protocol;
+mod rng;
+mod spatial;
+mod spells;
+mod status_effects;
+mod support;
+mod units;
+
+pub use bot::{Battlefield, Bot, Difficulty, BOT_DECK};
+pub use clock::{Tick, TICK};
+pub use elixir::Elixir;
+pub use hash::StateHash;
+pub use headless::HeadlessMatch;
+pub use input::{check_play, CardPlay, PendingPlays, StartMatch};
+pub use output::SimEvent;
+pub use protocol::{Recipients, Snapshot};
+pub use rng::SimRng;
+
+/// Порядок шагов внутри тика. Конфликтующие системы без явного порядка
+/// bevy запускает в разном порядке от запуска к запуску, и матч расходится
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum SimSet {
+    Effects,
+    Ai,
+    Abilities,
+    Units,
+    Projectiles,
+    Spells,
+    Support,
+    // Урон и гибель считаются после всех, кто мог его нанести
+    Health,
+}
+
+/// Данные баланса (UnitsData и CardsData) добавляет вызывающий.
+/// SimRng с нужным seed нужно вставить до первого тика
+pub fn plugin(app: &mut App) {
+    app.init_resource::<SimRng>();
+    app.configure_sets(
+        FixedUpdate,
+        (
+            SimSet::Effects,
+            SimSet::Ai,
+            SimSet::Abilities,
+            SimSet::Units,
+            SimSet::Projectiles,
+            SimSet::Spells,
+            SimSet::Support,
+            SimSet::Health,
+        )
+            .chain(),
+    );
+
+    app.add_plugins((
+        clock::plugin,
+        elixir::plugin,
+        hash::plugin,
+        input::plugin,
+        output::plugin,
+        spatial::plugin,
+        ai::plugin,
+        abilities::plugin,
+        units::plugin,
+        projectiles::plugin,
+        spells::plugin,
+        knockback::plugin,
+        status_effects::plugin,
+        support::plugin,
+    ));
+}
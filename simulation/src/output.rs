@@ -0,0 +1,73 @@
+use bevy::prelude::*;
+use common::{ArenaPos, Card, Level, PlayerNumber, Projectile, Spell, Unit};
+
+use crate::support::Hits;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_event::<SimEvent>();
+}
+
+/// Всё, что происходит в матче и должно дойти до клиентов или наблюдателей
+#[derive(Event, Debug, Clone)]
+pub enum SimEvent {
+    UnitSpawned {
+        entity: Entity,
+        unit: Unit,
+        pos: ArenaPos,
+        owner: PlayerNumber,
+        level: Level,
+    },
+    ProjectileSpawned {
+        entity: Entity,
+        projectile: Projectile,
+        attacker: Entity,
+        receiver: Entity,
+        pos: ArenaPos,
+    },
+    SpellSpawned {
+        entity: Entity,
+        spell: Spell,
+        pos: ArenaPos,
+        target: ArenaPos,
+    },
+    Despawned(Entity),
+    UnitDied(Entity),
+    // Потеря здоровья и щита за тик от одной карты. У урона башен карты нет
+    Damaged {
+        entity: Entity,
+        amount: u16,
+        source: Option<Card>,
+    },
+    KingTowerActivated(Entity),
+    ElixirAwarded {
+        player: PlayerNumber,
+        amount: u8,
+    },
+    // Башня короля разрушена
+    MatchEnded {
+        winner: PlayerNumber,
+    },
+}
+
+/// Урон собирается за весь тик, по событию на каждую нанёсшую его карту
+pub(crate) fn report_damage(
+    mut units: Query<(Entity, &mut Hits)>,
+    mut events: EventWriter<SimEvent>,
+) {
+    for (entity, mut hits) in &mut units {
+        let mut by_source: Vec<(Option<Card>, u16)> = Vec::new();
+        for (source, amount) in hits.0.drain(..) {
+            match by_source.iter_mut().find(|(s, _)| *s == source) {
+                Some((_, total)) => *total = total.saturating_add(amount),
+                None => by_source.push((source, amount)),
+            }
+        }
+        for (source, amount) in by_source {
+            events.send(SimEvent::Damaged {
+                entity,
+                amount,
+                source,
+            });
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use bevy::prelude::*;
+use common::{ArenaPos, PlayerNumber, Projectile};
+
+use crate::{
+    ai::Movement,
+    knockback::{Knockback, KnockbackForce},
+    spatial::SpatialIndex,
+    status_effects::{apply_effect, OnHit, StatusEffects},
+    support::Damageable,
+    units::{Hitbox, Origin, UnitType},
+    SimEvent,
+};
+
+use super::{ProjectileDamage, ProjectileRadius, ProjectileSet};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(spawn_bomb);
+
+    app.add_systems(FixedUpdate, update_bombs.in_set(ProjectileSet::Bombs));
+}
+
+#[derive(Event)]
+pub struct SpawnBomb(pub Entity, pub Entity, pub ArenaPos, pub u16);
+
+#[derive(Component)]
+#[require(
+    Projectile(|| Projectile::Bomb),
+    ProjectileRadius(|| ProjectileRadius(1.)),
+)]
+struct Bomb(Entity);
+
+fn spawn_bomb(
+    trigger: Trigger<SpawnBomb>,
+    on_hit: Query<&OnHit>,
+    origins: Query<&Origin>,
+    knockback: Query<&KnockbackForce>,
+    mut events: EventWriter<SimEvent>,
+    mut cmd: Commands,
+) {
+    let &SpawnBomb(attacker, receiver, pos, damage) = trigger.event();
+
+    let entity = cmd
+        .spawn((
+            Bomb(receiver),
+            pos,
+            ProjectileDamage(damage, origins.get(attacker).ok().map(|o| o.0)),
+            Movement {
+                target: Some(receiver),
+                speed: 15.,
+            },
+        ))
+        .id();
+    if let Ok(&on_hit) = on_hit.get(attacker) {
+        cmd.entity(entity).insert(on_hit);
+    }
+    if let Ok(&knockback) = knockback.get(attacker) {
+        cmd.entity(entity).insert(knockback);
+    }
+
+    events.send(SimEvent::ProjectileSpawned {
+        entity,
+        projectile: Projectile::Bomb,
+        attacker,
+        receiver,
+        pos,
+    });
+}
+
+fn update_bombs(
+    mut bombs: Query<
+        (
+            Entity,
+            &Bomb,
+            &ProjectileRadius,
+            &ProjectileDamage,
+            Option<&OnHit>,
+            Option<&KnockbackForce>,
+            &mut ArenaPos,
+        ),
+        Without<PlayerNumber>,
+    >,
+    mut units: Query<(
+        Entity,
+        &ArenaPos,
+        Damageable,
+        &Hitbox,
+        &UnitType,
+        &PlayerNumber,
+        Option<&mut StatusEffects>,
+    )>,
+    index: Res<SpatialIndex>,
+    mut cmd: Commands,
+    mut events: EventWriter<SimEvent>,
+) {
+    for (entity, bomb, radius, damage, on_hit, knockback, pos) in &mut bombs {
+        let Ok((_, recv_pos, _, hitbox, _, &side, _)) = units.get_mut(bomb.0) else {
+            // Цель умерла
+            cmd.entity(entity).despawn();
+            events.send(SimEvent::Despawned(entity));
+            continue;
+        };
+
+        if pos.distance(recv_pos) > radius.0 + hitbox.0 {
+            continue;
+        }
+
+        let mut targets = units.iter_many_mut(index.units.nearby(*pos, radius.0));
+        while let Some((
+            unit,
+            recv_pos,
+            mut recv_health,
+            hitbox,
+            unit_type,
+            &player_num,
+            effects,
+        )) = targets.fetch_next()
+        {
+            if let UnitType::Air = unit_type {
+                continue;
+            }
+            // Свои юниты рядом с целью не задеваются
+            if player_num != side || pos.distance(recv_pos) > radius.0 + hitbox.0 {
+                continue;
+            }
+            recv_health.damage(damage.0, damage.1);
+            if let Some(&OnHit(effect, duration)) = on_hit {
+                apply_effect(unit, effects, (effect, duration), &mut cmd);
+            }
+        }
+        // Отбрасывает юнитов той стороны, в которую летела бомба
+        if let Some(force) = knockback {
+            cmd.trigger(Knockback {
+                center: *pos,
+                radius: radius.0,
+                force: force.0,
+                side,
+                ground_only: true,
+            });
+        }
+        cmd.entity(entity).despawn();
+        events.send(SimEvent::Despawned(entity));
+    }
+}
@@ -0,0 +1,101 @@
+use bevy::prelude::*;
+use common::{ArenaPos, PlayerNumber, Projectile};
+
+use crate::{
+    ai::Movement,
+    status_effects::{apply_effect, OnHit, StatusEffects},
+    support::Damageable,
+    units::{Hitbox, Origin},
+    SimEvent,
+};
+
+use super::{ProjectileDamage, ProjectileRadius, ProjectileSet};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(spawn_bullet);
+
+    app.add_systems(FixedUpdate, update_bullets.in_set(ProjectileSet::Bullets));
+}
+
+#[derive(Event)]
+pub struct SpawnBullet(pub Entity, pub Entity, pub ArenaPos, pub u16);
+
+#[derive(Component)]
+#[require(
+    Projectile(|| Projectile::Bullet),
+    ProjectileRadius(|| ProjectileRadius(0.2)),
+)]
+struct Bullet(Entity);
+
+fn spawn_bullet(
+    trigger: Trigger<SpawnBullet>,
+    on_hit: Query<&OnHit>,
+    origins: Query<&Origin>,
+    mut events: EventWriter<SimEvent>,
+    mut cmd: Commands,
+) {
+    let &SpawnBullet(attacker, receiver, pos, damage) = trigger.event();
+
+    let entity = cmd
+        .spawn((
+            Bullet(receiver),
+            pos,
+            ProjectileDamage(damage, origins.get(attacker).ok().map(|o| o.0)),
+            Movement {
+                target: Some(receiver),
+                speed: 40.,
+            },
+        ))
+        .id();
+    if let Ok(&on_hit) = on_hit.get(attacker) {
+        cmd.entity(entity).insert(on_hit);
+    }
+
+    events.send(SimEvent::ProjectileSpawned {
+        entity,
+        projectile: Projectile::Bullet,
+        attacker,
+        receiver,
+        pos,
+    });
+}
+
+fn update_bullets(
+    mut bullets: Query<
+        (
+            Entity,
+            &Bullet,
+            &ProjectileRadius,
+            &ProjectileDamage,
+            Option<&OnHit>,
+            &mut ArenaPos,
+        ),
+        Without<PlayerNumber>,
+    >,
+    mut units: Query<
+        (&ArenaPos, Damageable, &Hitbox, Option<&mut StatusEffects>),
+        With<PlayerNumber>,
+    >,
+    mut cmd: Commands,
+    mut events: EventWriter<SimEvent>,
+) {
+    for (entity, bullet, radius, damage, on_hit, pos) in &mut bullets {
+        let Ok((recv_pos, mut recv_health, hitbox, effects)) = units.get_mut(bullet.0) else {
+            // Цель умерла
+            cmd.entity(entity).despawn();
+            events.send(SimEvent::Despawned(entity));
+            continue;
+        };
+
+        if pos.distance(recv_pos) > radius.0 + hitbox.0 {
+            continue;
+        }
+
+        recv_health.damage(damage.0, damage.1);
+        if let Some(&OnHit(effect, duration)) = on_hit {
+            apply_effect(bullet.0, effects, (effect, duration), &mut cmd);
+        }
+        cmd.entity(entity).despawn();
+        events.send(SimEvent::Despawned(entity));
+    }
+}
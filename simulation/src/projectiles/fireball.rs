@@ -0,0 +1,135 @@
+use bevy::prelude::*;
+use common::{ArenaPos, PlayerNumber, Projectile};
+
+use crate::{
+    ai::Movement,
+    knockback::{Knockback, KnockbackForce},
+    spatial::SpatialIndex,
+    status_effects::{apply_effect, OnHit, StatusEffects},
+    support::Damageable,
+    units::{Hitbox, Origin},
+    SimEvent,
+};
+
+use super::{ProjectileDamage, ProjectileRadius, ProjectileSet};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(spawn_fireball);
+
+    app.add_systems(
+        FixedUpdate,
+        update_fireballs.in_set(ProjectileSet::Fireballs),
+    );
+}
+
+#[derive(Event)]
+pub struct SpawnFireball(pub Entity, pub Entity, pub ArenaPos, pub u16);
+
+#[derive(Component)]
+#[require(
+    Projectile(|| Projectile::Fireball),
+    ProjectileRadius(|| ProjectileRadius(1.)),
+)]
+struct Fireball(Entity);
+
+fn spawn_fireball(
+    trigger: Trigger<SpawnFireball>,
+    on_hit: Query<&OnHit>,
+    origins: Query<&Origin>,
+    knockback: Query<&KnockbackForce>,
+    mut events: EventWriter<SimEvent>,
+    mut cmd: Commands,
+) {
+    let &SpawnFireball(attacker, receiver, pos, damage) = trigger.event();
+
+    let entity = cmd
+        .spawn((
+            Fireball(receiver),
+            pos,
+            ProjectileDamage(damage, origins.get(attacker).ok().map(|o| o.0)),
+            Movement {
+                target: Some(receiver),
+                speed: 10.,
+            },
+        ))
+        .id();
+    if let Ok(&on_hit) = on_hit.get(attacker) {
+        cmd.entity(entity).insert(on_hit);
+    }
+    if let Ok(&knockback) = knockback.get(attacker) {
+        cmd.entity(entity).insert(knockback);
+    }
+
+    events.send(SimEvent::ProjectileSpawned {
+        entity,
+        projectile: Projectile::Fireball,
+        attacker,
+        receiver,
+        pos,
+    });
+}
+
+fn update_fireballs(
+    mut fireballs: Query<
+        (
+            Entity,
+            &Fireball,
+            &ProjectileRadius,
+            &ProjectileDamage,
+            Option<&OnHit>,
+            Option<&KnockbackForce>,
+            &mut ArenaPos,
+        ),
+        Without<PlayerNumber>,
+    >,
+    mut units: Query<(
+        Entity,
+        &ArenaPos,
+        Damageable,
+        &Hitbox,
+        &PlayerNumber,
+        Option<&mut StatusEffects>,
+    )>,
+    index: Res<SpatialIndex>,
+    mut cmd: Commands,
+    mut events: EventWriter<SimEvent>,
+) {
+    for (entity, fireball, radius, damage, on_hit, knockback, pos) in &mut fireballs {
+        let Ok((_, recv_pos, _, hitbox, &side, _)) = units.get_mut(fireball.0) else {
+            // Цель умерла
+            cmd.entity(entity).despawn();
+            events.send(SimEvent::Despawned(entity));
+            continue;
+        };
+
+        if pos.distance(recv_pos) > radius.0 + hitbox.0 {
+            continue;
+        }
+
+        let mut targets = units.iter_many_mut(index.units.nearby(*pos, radius.0));
+        while let Some((unit, recv_pos, mut recv_health, hitbox, &player_num, effects)) =
+            targets.fetch_next()
+        {
+            // Свои юниты рядом с целью не задеваются
+            if player_num != side || pos.distance(recv_pos) > radius.0 + hitbox.0 {
+                continue;
+            }
+            recv_health.damage(damage.0, damage.1);
+            if let Some(&OnHit(effect, duration)) = on_hit {
+                apply_effect(unit, effects, (effect, duration), &mut cmd);
+            }
+        }
+        // Отбрасывает юнитов той стороны, в которую летел снаряд
+        if let Some(force) = knockback {
+            cmd.trigger(Knockback {
+                center: *pos,
+                radius: radius.0,
+                force: force.0,
+                side,
+                ground_only: false,
+            });
+        }
+        cmd.entity(entity).despawn();
+        events.send(SimEvent::Despawned(entity));
+    }
+}
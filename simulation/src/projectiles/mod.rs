@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+use bomb::SpawnBomb;
+use bullet::SpawnBullet;
+use common::{ArenaPos, Card, Projectile};
+use fireball::SpawnFireball;
+
+use crate::SimSet;
+
+mod bomb;
+mod bullet;
+mod fireball;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_plugins((bullet::plugin, fireball::plugin, bomb::plugin));
+
+    app.configure_sets(
+        FixedUpdate,
+        (
+            ProjectileSet::Bullets,
+            ProjectileSet::Fireballs,
+            ProjectileSet::Bombs,
+        )
+            .chain()
+            .in_set(SimSet::Projectiles),
+    );
+}
+
+/// Снаряды разных типов бьют по одним и тем же юнитам, поэтому тоже идут по порядку
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum ProjectileSet {
+    Bullets,
+    Fireballs,
+    Bombs,
+}
+
+#[derive(Component)]
+struct ProjectileRadius(pub f32);
+
+/// Урон уже с учётом уровня атакующего и карта атакующего
+#[derive(Component)]
+struct ProjectileDamage(pub u16, pub Option<Card>);
+
+pub(super) trait SpawnProjectile {
+    fn spawn(
+        &self,
+        attacker: Entity,
+        receiver: Entity,
+        pos: ArenaPos,
+        damage: u16,
+        cmd: &mut Commands,
+    );
+}
+
+impl SpawnProjectile for Projectile {
+    fn spawn(
+        &self,
+        attacker: Entity,
+        receiver: Entity,
+        pos: ArenaPos,
+        damage: u16,
+        cmd: &mut Commands,
+    ) {
+        match self {
+            Projectile::Bullet => cmd.trigger(SpawnBullet(attacker, receiver, pos, damage)),
+            Projectile::Fireball => {
+                cmd.trigger(SpawnFireball(attacker, receiver, pos, damage))
+            }
+            Projectile::Bomb => cmd.trigger(SpawnBomb(attacker, receiver, pos, damage)),
+        }
+    }
+}
@@ -0,0 +1,217 @@
+//! Перевод событий и состояния симуляции в сообщения клиентам.
+//! Им пользуются и сервер, и клиент при локальной симуляции
+
+use std::f32::consts::PI;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use common::{
+    ArenaPos, Checksum, ChecksumEntry, Direction, Health, PlayerNumber, ServerMessage, Shield,
+    UnitState, CHECKSUM_INTERVAL,
+};
+
+use crate::{
+    abilities::Dash,
+    ai::{Attack, Movement},
+    status_effects::StatusEffects,
+    SimEvent, Tick,
+};
+
+/// Кому из игроков адресовано сообщение
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Recipients {
+    All,
+    Player(PlayerNumber),
+}
+impl Recipients {
+    pub fn includes(self, player: PlayerNumber) -> bool {
+        match self {
+            Recipients::All => true,
+            Recipients::Player(p) => p == player,
+        }
+    }
+}
+
+impl SimEvent {
+    /// None для событий, о которых клиентам сообщать не нужно
+    pub fn message(&self) -> Option<(Recipients, ServerMessage)> {
+        let message = match *self {
+            SimEvent::UnitSpawned {
+                entity,
+                unit,
+                pos,
+                owner,
+                level,
+            } => ServerMessage::SpawnUnit {
+                server_entity: entity,
+                unit,
+                pos,
+                owner,
+                level,
+            },
+            SimEvent::ProjectileSpawned {
+                entity,
+                projectile,
+                attacker,
+                receiver,
+                pos,
+            } => ServerMessage::SpawnProjectile {
+                server_entity: entity,
+                projectile,
+                attacker,
+                receiver,
+                pos,
+            },
+            SimEvent::SpellSpawned {
+                entity,
+                spell,
+                pos,
+                target,
+            } => ServerMessage::SpawnSpell {
+                server_entity: entity,
+                spell,
+                pos,
+                target,
+            },
+            SimEvent::Despawned(entity) => ServerMessage::Despawn(entity),
+            SimEvent::UnitDied(entity) => ServerMessage::UnitDied(entity),
+            SimEvent::KingTowerActivated(entity) => ServerMessage::ActivateKingTower(entity),
+            SimEvent::ElixirAwarded { player, amount } => {
+                return Some((Recipients::Player(player), ServerMessage::AddElixir(amount)));
+            }
+            // Клиент видит урон по здоровью в SyncEntities
+            SimEvent::Damaged { .. } => return None,
+            // Клиенты конец матча пока не показывают
+            SimEvent::MatchEnded { .. } => return None,
+        };
+        Some((Recipients::All, message))
+    }
+}
+
+trait DefaultDirection {
+    fn default_direction(&self) -> Direction;
+}
+impl DefaultDirection for PlayerNumber {
+    fn default_direction(&self) -> Direction {
+        match self {
+            PlayerNumber::One => Direction::Up,
+            PlayerNumber::Two => Direction::Down,
+        }
+    }
+}
+
+fn calc_direction(direction: &ArenaPos) -> Direction {
+    let mut angle = direction.0.acos() * 180. / PI;
+    if direction.1 < 0. {
+        angle = -angle + 360.;
+    }
+
+    match angle {
+        0.0..20. | 340.0..360. => Direction::Right,
+        20.0..160. => Direction::Up,
+        160.0..200. => Direction::Left,
+        200.0..340. => Direction::Down,
+        _ => Direction::Right,
+    }
+}
+
+/// Положения, здоровье и состояния всех юнитов и снарядов для SyncEntities.
+/// Контрольная сумма считается по тем же юнитам, что уходят в сообщение
+#[derive(SystemParam)]
+pub struct Snapshot<'w, 's> {
+    units: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static ArenaPos,
+            &'static UnitState,
+            Option<&'static Attack>,
+            Option<&'static Movement>,
+            Option<&'static Dash>,
+            &'static PlayerNumber,
+            &'static Health,
+            Option<&'static Shield>,
+            Option<&'static StatusEffects>,
+        ),
+    >,
+    projectiles: Query<'w, 's, (Entity, &'static ArenaPos), Without<PlayerNumber>>,
+    positions: Query<'w, 's, &'static ArenaPos>,
+    tick: Res<'w, Tick>,
+}
+impl Snapshot<'_, '_> {
+    pub fn message(&self) -> ServerMessage {
+        let mut u = Vec::new();
+        for (
+            entity,
+            pos,
+            state,
+            attack,
+            movement,
+            dash,
+            player_num,
+            health,
+            shield,
+            effects,
+        ) in &self.units
+        {
+            let direction = match state {
+                UnitState::Idle | UnitState::Deploying => player_num.default_direction(),
+                UnitState::Moving | UnitState::Charging => {
+                    let movement = movement.unwrap();
+                    match movement.target {
+                        Some(m) => {
+                            let Ok(target_pos) = self.positions.get(m) else {
+                                continue;
+                            };
+                            calc_direction(&pos.direction(target_pos))
+                        }
+                        None => player_num.default_direction(),
+                    }
+                }
+                // Атаки нет только у построек, которые спавнят юнитов
+                UnitState::Attacking => match attack.and_then(|a| a.target) {
+                    Some(a) => {
+                        let Ok(target_pos) = self.positions.get(a) else {
+                            continue;
+                        };
+                        calc_direction(&pos.direction(target_pos))
+                    }
+                    None => player_num.default_direction(),
+                },
+                UnitState::Dashing => match dash.and_then(|d| d.target) {
+                    Some(d) => {
+                        let Ok(target_pos) = self.positions.get(d) else {
+                            continue;
+                        };
+                        calc_direction(&pos.direction(target_pos))
+                    }
+                    None => player_num.default_direction(),
+                },
+            };
+            let effects = effects.map(StatusEffects::active).unwrap_or_default();
+            let shield = shield.copied().unwrap_or_default();
+            u.push((entity, *pos, direction, *state, *health, shield, effects));
+        }
+
+        let mut p = Vec::new();
+        for (entity, position) in &self.projectiles {
+            p.push((entity, *position));
+        }
+
+        let checksum = self.tick.0.is_multiple_of(CHECKSUM_INTERVAL).then(|| {
+            let mut entries: Vec<_> = u
+                .iter()
+                .map(|&(entity, pos, _, state, health, ..)| {
+                    ChecksumEntry::new(entity, pos, state, health)
+                })
+                .collect();
+            Checksum::new(self.tick.0, &mut entries)
+        });
+
+        ServerMessage::SyncEntities {
+            units: u,
+            projectiles: p,
+            checksum,
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+
+/// Единственный источник случайности в симуляции.
+/// Одинаковый seed и одинаковые розыгрыши дают одинаковый матч
+#[derive(Resource, Clone, Debug)]
+pub struct SimRng {
+    seed: u64,
+    state: u64,
+}
+impl SimRng {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, state: seed }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// SplitMix64: не зависит от платформы и сторонних крейтов
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Число в [0, 1)
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Число в [0, n)
+    pub fn below(&mut self, n: u32) -> u32 {
+        (((self.next_u64() >> 32) * n as u64) >> 32) as u32
+    }
+}
+impl Default for SimRng {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
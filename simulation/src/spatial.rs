@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+use common::{ArenaPos, SpatialGrid};
+
+use crate::units::{Hitbox, UnitType};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<SpatialIndex>();
+    app.add_systems(FixedPreUpdate, rebuild_spatial_index);
+}
+
+/// Размер ячейки в клетках арены, подобран по cargo bench -p common
+const CELL_SIZE: f32 = 2.;
+
+/// Положения юнитов на начало тика. Через него идут все поиски целей
+/// по дальности, радиусу агрессии и области поражения
+#[derive(Resource)]
+pub struct SpatialIndex {
+    pub units: SpatialGrid,
+    // Построек мало, для поиска ближайшей хватает простого списка
+    pub buildings: Vec<Entity>,
+}
+impl Default for SpatialIndex {
+    fn default() -> Self {
+        Self {
+            units: SpatialGrid::new(CELL_SIZE),
+            buildings: Vec::new(),
+        }
+    }
+}
+
+fn rebuild_spatial_index(
+    mut index: ResMut<SpatialIndex>,
+    units: Query<(Entity, &ArenaPos, &Hitbox, &UnitType)>,
+) {
+    let index = &mut *index;
+    index.units.clear();
+    index.buildings.clear();
+    // Порядок обхода запроса зависит от истории архетипов, а от порядка в сетке
+    // зависит выбор цели. Сортировка делает его одинаковым в любом прогоне
+    let mut units: Vec<_> = units.iter().collect();
+    units.sort_unstable_by_key(|(entity, ..)| *entity);
+    for (entity, &pos, hitbox, unit_type) in units {
+        index.units.insert(entity, pos, hitbox.0);
+        if *unit_type == UnitType::Building {
+            index.buildings.push(entity);
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use bevy::prelude::*;
+use common::{
+    ArenaPos, Card, CardsData, Level, PlayerNumber, Spell, SpellStats, StatusEffect,
+};
+
+use crate::{
+    ai::Attack,
+    clock::TICK,
+    knockback::Knockback,
+    spatial::SpatialIndex,
+    status_effects::{apply_effect, StatusEffects},
+    support::Damageable,
+    units::{Hitbox, KingTower, Origin, UnitType},
+    SimEvent, SimSet,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(spawn_spell);
+
+    app.add_systems(
+        FixedUpdate,
+        (update_spell_flight, resolve_spells)
+            .chain()
+            .in_set(SimSet::Spells),
+    );
+}
+
+/// Игрок, разыгравший заклинание.
+/// Не PlayerNumber, так как по нему запросы отличают юнитов от снарядов
+#[derive(Component)]
+struct Caster(PlayerNumber);
+
+/// Заклинание летит от башни короля к точке розыгрыша
+#[derive(Component)]
+struct SpellFlight {
+    target: ArenaPos,
+    speed: f32,
+}
+
+#[derive(Component, Deref)]
+struct SpellArea(SpellStats);
+
+#[derive(Event)]
+pub struct SpawnSpellEvent(pub Spell, pub ArenaPos, pub PlayerNumber, pub Level);
+
+/// Все заклинания собираются из характеристик в cards.ron
+fn spawn_spell(
+    trigger: Trigger<SpawnSpellEvent>,
+    cards: Res<CardsData>,
+    king_towers: Query<(&ArenaPos, &PlayerNumber), With<KingTower>>,
+    mut events: EventWriter<SimEvent>,
+    mut cmd: Commands,
+) {
+    let &SpawnSpellEvent(spell, target, owner, level) = trigger.event();
+    let Some((&pos, _)) = king_towers.iter().find(|(_, n)| **n == owner) else {
+        // Башня короля разрушена, игра окончена
+        return;
+    };
+    let stats = cards.spell(spell);
+
+    let entity = cmd
+        .spawn((
+            spell,
+            Origin(Card::from(spell)),
+            pos,
+            Caster(owner),
+            level,
+            SpellFlight {
+                target,
+                speed: stats.speed,
+            },
+            SpellArea(stats.clone()),
+        ))
+        .id();
+
+    events.send(SimEvent::SpellSpawned {
+        entity,
+        spell,
+        pos,
+        target,
+    });
+}
+
+fn update_spell_flight(mut spells: Query<(&mut ArenaPos, &SpellFlight)>) {
+    for (mut pos, flight) in &mut spells {
+        let step = flight.speed * TICK.as_secs_f32();
+        if pos.distance(&flight.target) <= step {
+            *pos = flight.target;
+            continue;
+        }
+        let direction = pos.direction(&flight.target);
+        *pos += direction.mul(step);
+    }
+}
+
+fn resolve_spells(
+    spells: Query<(
+        Entity,
+        &ArenaPos,
+        &SpellFlight,
+        &SpellArea,
+        &Caster,
+        &Level,
+        &Origin,
+    )>,
+    mut units: Query<(
+        Entity,
+        &ArenaPos,
+        Damageable,
+        &Hitbox,
+        &PlayerNumber,
+        &UnitType,
+        Option<&mut StatusEffects>,
+        Option<&mut Attack>,
+    )>,
+    index: Res<SpatialIndex>,
+    mut events: EventWriter<SimEvent>,
+    mut cmd: Commands,
+) {
+    for (entity, pos, flight, area, caster, level, origin) in &spells {
+        if pos.distance(&flight.target) > 0.01 {
+            continue;
+        }
+
+        let mut targets = units.iter_many_mut(index.units.nearby(*pos, area.radius));
+        while let Some((
+            unit,
+            unit_pos,
+            mut health,
+            hitbox,
+            player_num,
+            unit_type,
+            effects,
+            attack,
+        )) = targets.fetch_next()
+        {
+            if (*player_num == caster.0) != area.allies {
+                continue;
+            }
+            if pos.distance(unit_pos) > area.radius + hitbox.0 {
+                continue;
+            }
+            let damage = match unit_type {
+                UnitType::Building => level.scale(area.tower_damage),
+                UnitType::Air | UnitType::Ground => level.scale(area.damage),
+            };
+            health.damage(damage, Some(origin.0));
+
+            let Some(effect) = area.effect else {
+                continue;
+            };
+            if let (StatusEffect::Stun, Some(mut attack)) = (effect.0, attack) {
+                // Оглушение сбрасывает перезарядку атаки
+                attack.cooldown_timer.reset();
+            }
+            apply_effect(unit, effects, effect, &mut cmd);
+        }
+
+        if area.knockback > 0. && !area.allies {
+            cmd.trigger(Knockback {
+                center: *pos,
+                radius: area.radius,
+                force: area.knockback,
+                side: caster.0.opponent(),
+                ground_only: false,
+            });
+        }
+
+        cmd.entity(entity).despawn();
+        events.send(SimEvent::Despawned(entity));
+    }
+}
+
+pub(super) trait SpawnSpell {
+    fn spawn(
+        &self,
+        target: ArenaPos,
+        player_num: PlayerNumber,
+        level: Level,
+        cmd: &mut Commands,
+    );
+}
+
+impl SpawnSpell for Spell {
+    fn spawn(
+        &self,
+        target: ArenaPos,
+        player_num: PlayerNumber,
+        level: Level,
+        cmd: &mut Commands,
+    ) {
+        cmd.trigger(SpawnSpellEvent(*self, target, player_num, level));
+    }
+}
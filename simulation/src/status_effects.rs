@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+use common::{ActiveEffects, StatusEffect};
+
+use crate::{clock::TICK, SimSet};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(FixedUpdate, update_status_effects.in_set(SimSet::Effects));
+}
+
+/// Временные эффекты от заклинаний и снарядов
+#[derive(Component, Default)]
+pub struct StatusEffects(Vec<(StatusEffect, Timer)>);
+impl StatusEffects {
+    /// Одинаковые эффекты не складываются, остаётся наибольшая длительность.
+    /// Разные эффекты действуют одновременно
+    pub fn apply(&mut self, effect: StatusEffect, duration: f32) {
+        let new_timer = Timer::from_seconds(duration, TimerMode::Once);
+        match self.0.iter_mut().find(|(e, _)| *e == effect) {
+            Some((_, timer)) => {
+                if timer.remaining_secs() < duration {
+                    *timer = new_timer;
+                }
+            }
+            None => self.0.push((effect, new_timer)),
+        }
+    }
+
+    /// Множитель скорости передвижения и перезарядки атаки
+    pub fn rate(&self) -> f32 {
+        self.0.iter().fold(1., |rate, (effect, _)| {
+            rate * match effect {
+                StatusEffect::Stun | StatusEffect::Freeze => 0.,
+                StatusEffect::Slow => 0.65,
+                StatusEffect::Rage => 1.35,
+            }
+        })
+    }
+
+    pub fn active(&self) -> ActiveEffects {
+        let mut active = ActiveEffects::default();
+        for (effect, _) in &self.0 {
+            active.insert(*effect);
+        }
+        active
+    }
+}
+
+/// Эффект, который снаряды юнита накладывают на цель, и его длительность в секундах
+#[derive(Component, Clone, Copy)]
+pub struct OnHit(pub StatusEffect, pub f32);
+
+/// StatusEffects добавляется при первом эффекте на сущности
+pub fn apply_effect(
+    entity: Entity,
+    effects: Option<Mut<StatusEffects>>,
+    (effect, duration): (StatusEffect, f32),
+    cmd: &mut Commands,
+) {
+    match effects {
+        Some(mut effects) => effects.apply(effect, duration),
+        None => {
+            let mut effects = StatusEffects::default();
+            effects.apply(effect, duration);
+            cmd.entity(entity).insert(effects);
+        }
+    }
+}
+
+/// Множитель для сущностей, на которых эффектов никогда не было
+pub fn effects_rate(effects: Option<&StatusEffects>) -> f32 {
+    effects.map_or(1., StatusEffects::rate)
+}
+
+fn update_status_effects(mut query: Query<&mut StatusEffects>) {
+    for mut effects in &mut query {
+        effects
+            .0
+            .retain_mut(|(_, timer)| !timer.tick(TICK).finished());
+    }
+}
@@ -0,0 +1,95 @@
+use bevy::{ecs::query::QueryData, prelude::*};
+use common::{ArenaPos, Card, HealStats, Health, Level, PlayerNumber, Shield};
+
+use crate::{
+    ai::DeployTimer,
+    clock::TICK,
+    spatial::SpatialIndex,
+    status_effects::{effects_rate, StatusEffects},
+    units::UnitType,
+    SimSet,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(FixedUpdate, update_healers.in_set(SimSet::Support));
+}
+
+/// Урон за тик с картами, которые его нанесли. Его собирает report_damage
+#[derive(Component, Default)]
+pub struct Hits(pub(crate) Vec<(Option<Card>, u16)>);
+
+/// Здоровье вместе со щитом. Весь урон наносится через него
+#[derive(QueryData)]
+#[query_data(mutable)]
+pub struct Damageable {
+    pub health: &'static mut Health,
+    pub shield: Option<&'static mut Shield>,
+    hits: &'static mut Hits,
+}
+impl DamageableItem<'_> {
+    /// Урон сначала поглощается щитом. source - карта нападающего, у башен её нет
+    pub fn damage(&mut self, amount: u16, source: Option<Card>) {
+        let (absorbed, amount) = match self.shield.as_mut() {
+            Some(shield) => {
+                let absorbed = amount.min(shield.0);
+                shield.0 -= absorbed;
+                (absorbed, amount - absorbed)
+            }
+            None => (0, amount),
+        };
+        let lost = amount.min(self.health.0);
+        self.health.0 -= lost;
+        if absorbed + lost > 0 {
+            self.hits.0.push((source, absorbed + lost));
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Healer {
+    amount: u16,
+    radius: f32,
+    timer: Timer,
+}
+impl Healer {
+    pub fn from_stats(stats: &HealStats, level: Level) -> Self {
+        Self {
+            amount: level.scale(stats.amount),
+            radius: stats.radius,
+            timer: Timer::from_seconds(stats.interval, TimerMode::Repeating),
+        }
+    }
+}
+
+fn update_healers(
+    mut healers: Query<
+        (
+            &mut Healer,
+            &ArenaPos,
+            &PlayerNumber,
+            Option<&StatusEffects>,
+        ),
+        Without<DeployTimer>,
+    >,
+    mut units: Query<(&ArenaPos, &PlayerNumber, &UnitType, &mut Health)>,
+    index: Res<SpatialIndex>,
+) {
+    for (mut healer, pos, owner, effects) in &mut healers {
+        let delta = TICK.mul_f32(effects_rate(effects));
+        if !healer.timer.tick(delta).just_finished() {
+            continue;
+        }
+
+        let mut targets = units.iter_many_mut(index.units.nearby(*pos, healer.radius));
+        while let Some((unit_pos, player_num, unit_type, mut health)) = targets.fetch_next() {
+            if player_num != owner || *unit_type == UnitType::Building {
+                continue;
+            }
+            // Погибший в этом кадре юнит уже не спасти
+            if health.0 == 0 || pos.distance(unit_pos) > healer.radius {
+                continue;
+            }
+            health.0 = health.0.saturating_add(healer.amount).min(health.1);
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use common::{ArenaPos, DeathEffect, Level, PlayerNumber, Unit, UnitsData};
+
+use crate::{elixir::Elixir, spatial::SpatialIndex, support::Damageable, SimEvent, SimRng};
+
+use super::{Hitbox, Origin, SpawnUnit};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(kill_unit);
+}
+
+/// Здоровье юнита закончилось
+#[derive(Event)]
+pub struct UnitDied(pub Entity);
+
+/// Юниты из DeathEffect::Spawn расставляются по кругу такого радиуса,
+/// начиная со случайного угла
+const DEATH_SPAWN_RADIUS: f32 = 0.6;
+
+/// Эффекты гибели выполняются до того, как клиенты узнают о смерти юнита
+fn kill_unit(
+    trigger: Trigger<UnitDied>,
+    dead: Query<(&Unit, &ArenaPos, &PlayerNumber, &Level, Option<&Origin>)>,
+    mut units: Query<(&ArenaPos, &PlayerNumber, &Hitbox, Damageable)>,
+    units_data: Res<UnitsData>,
+    index: Res<SpatialIndex>,
+    mut rng: ResMut<SimRng>,
+    mut elixir: ResMut<Elixir>,
+    mut events: EventWriter<SimEvent>,
+    mut cmd: Commands,
+) {
+    let UnitDied(entity) = *trigger.event();
+    // Юнит мог погибнуть от эффекта другого юнита в этом же кадре
+    let Ok((&unit, &pos, &owner, &level, origin)) = dead.get(entity) else {
+        return;
+    };
+    let origin = origin.map(|o| o.0);
+
+    for effect in &units_data.stats(unit).death {
+        match *effect {
+            DeathEffect::Spawn {
+                unit: spawned,
+                count,
+            } => {
+                let start = match count > 1 {
+                    true => TAU * rng.next_f32(),
+                    false => 0.,
+                };
+                for i in 0..count {
+                    let mut spawn_pos = pos;
+                    if count > 1 {
+                        let angle = start + TAU * i as f32 / count as f32;
+                        spawn_pos +=
+                            ArenaPos(angle.cos(), angle.sin()).mul(DEATH_SPAWN_RADIUS);
+                    }
+                    spawned.spawn(spawn_pos, owner, level, origin, &mut cmd);
+                }
+            }
+            DeathEffect::Damage { damage, radius } => {
+                let mut targets = units.iter_many_mut(index.units.nearby(pos, radius));
+                while let Some((unit_pos, player_num, hitbox, mut health)) =
+                    targets.fetch_next()
+                {
+                    if *player_num == owner || pos.distance(unit_pos) > radius + hitbox.0 {
+                        continue;
+                    }
+                    health.damage(level.scale(damage), origin);
+                }
+            }
+            DeathEffect::Elixir(amount) => {
+                elixir.add(owner.opponent(), amount);
+                events.send(SimEvent::ElixirAwarded {
+                    player: owner.opponent(),
+                    amount,
+                });
+            }
+        }
+    }
+
+    cmd.entity(entity).despawn();
+    events.send(SimEvent::UnitDied(entity));
+
+    if unit == Unit::KingTower {
+        events.send(SimEvent::MatchEnded {
+            winner: owner.opponent(),
+        });
+    }
+}
@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+use common::{Health, PlayerNumber, Shield};
+
+use crate::{SimEvent, SimSet};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(FixedUpdate, activate_king_towers.in_set(SimSet::Units));
+}
+
+#[derive(Component)]
+#[require(Dormant)]
+pub struct KingTower;
+
+#[derive(Component)]
+pub(super) struct ArcherTower;
+
+/// Башня короля не атакует, пока не получит урон или не будет разрушена
+/// одна из башен лучников её владельца
+#[derive(Component, Default)]
+pub struct Dormant;
+
+fn activate_king_towers(
+    king_towers: Query<
+        (Entity, &Health, Option<&Shield>, &PlayerNumber),
+        (With<KingTower>, With<Dormant>),
+    >,
+    archer_towers: Query<&PlayerNumber, With<ArcherTower>>,
+    mut events: EventWriter<SimEvent>,
+    mut cmd: Commands,
+) {
+    for (entity, health, shield, player_num) in &king_towers {
+        let archer_towers_left = archer_towers
+            .iter()
+            .filter(|owner| *owner == player_num)
+            .count();
+        // Урон по щиту тоже будит короля
+        let damaged = health.0 < health.1 || shield.is_some_and(|s| s.0 < s.1);
+        if !damaged && archer_towers_left == 2 {
+            continue;
+        }
+
+        cmd.entity(entity).remove::<Dormant>();
+        events.send(SimEvent::KingTowerActivated(entity));
+    }
+}
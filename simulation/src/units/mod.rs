@@ -0,0 +1,152 @@
+use bevy::prelude::*;
+pub use common::UnitType;
+use common::{
+    ArenaPos, Card, Health, Level, PlayerNumber, Shield, TowerKind, Unit, UnitState, UnitsData,
+};
+pub use death::UnitDied;
+use king_tower::ArcherTower;
+pub use king_tower::{Dormant, KingTower};
+use spawner::Spawner;
+
+use crate::{
+    abilities::{Charge, Dash},
+    ai::{AggroRadius, Attack, Decay, DeployTimer, Movement},
+    knockback::{KnockbackForce, Mass},
+    status_effects::OnHit,
+    support::{Healer, Hits},
+    SimEvent,
+};
+
+mod death;
+mod king_tower;
+mod spawner;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(spawn_unit);
+
+    app.add_plugins((death::plugin, king_tower::plugin, spawner::plugin));
+}
+
+#[derive(Component)]
+pub struct Hitbox(pub f32);
+
+/// Карта, из которой появился юнит, заклинание или снаряд. Юниты из построек
+/// и из эффектов гибели наследуют её. У башен карты нет
+#[derive(Component, Clone, Copy)]
+pub struct Origin(pub Card);
+
+#[derive(Event)]
+pub struct SpawnUnitEvent(
+    pub Unit,
+    pub ArenaPos,
+    pub PlayerNumber,
+    pub Level,
+    pub Option<Card>,
+);
+
+/// Все юниты собираются из характеристик в units.ron
+fn spawn_unit(
+    trigger: Trigger<SpawnUnitEvent>,
+    units: Res<UnitsData>,
+    mut events: EventWriter<SimEvent>,
+    mut cmd: Commands,
+) {
+    let &SpawnUnitEvent(unit, pos, owner, level, origin) = trigger.event();
+    let stats = units.stats(unit);
+    // Здоровье и урон растут с уровнем карты
+    let health = level.scale(stats.health);
+
+    let mut entity = cmd.spawn((
+        unit,
+        pos,
+        owner,
+        level,
+        Health::new(health),
+        Hits::default(),
+        stats.unit_type,
+        Hitbox(stats.hitbox),
+        Mass(stats.mass),
+    ));
+    if let Some(card) = origin {
+        entity.insert(Origin(card));
+    }
+    if let Some(speed) = stats.speed {
+        entity.insert(Movement::new(speed));
+    }
+    if let Some(aggro_radius) = stats.aggro_radius {
+        entity.insert(AggroRadius(aggro_radius));
+    }
+    if let Some(attack) = &stats.attack {
+        entity.insert(Attack::from_stats(attack, level));
+        if let Some((effect, duration)) = attack.on_hit {
+            entity.insert(OnHit(effect, duration));
+        }
+        if attack.knockback > 0. {
+            entity.insert(KnockbackForce(attack.knockback));
+        }
+    }
+    if let Some(lifetime) = stats.lifetime {
+        entity.insert(Decay::new(health, lifetime));
+    }
+    if let Some(spawner) = &stats.spawner {
+        entity.insert(Spawner::from(spawner));
+    }
+    if let Some(charge) = &stats.charge {
+        entity.insert(Charge::from(charge));
+    }
+    if let Some(dash) = &stats.dash {
+        entity.insert(Dash::from(dash));
+    }
+    if let Some(heal) = &stats.heal {
+        entity.insert(Healer::from_stats(heal, level));
+    }
+    if let Some(shield) = stats.shield {
+        entity.insert(Shield::new(level.scale(shield)));
+    }
+    match stats.tower {
+        Some(TowerKind::Archer) => entity.insert(ArcherTower),
+        Some(TowerKind::King) => entity.insert(KingTower),
+        None => &mut entity,
+    };
+
+    if stats.deploy_time > 0. {
+        entity.insert((UnitState::Deploying, DeployTimer::new(stats.deploy_time)));
+    } else if stats.speed.is_some() {
+        entity.insert(UnitState::Moving);
+    } else {
+        entity.insert(UnitState::Idle);
+    }
+    let entity = entity.id();
+
+    events.send(SimEvent::UnitSpawned {
+        entity,
+        unit,
+        pos,
+        owner,
+        level,
+    });
+}
+
+pub(super) trait SpawnUnit {
+    fn spawn(
+        &self,
+        pos: ArenaPos,
+        player_num: PlayerNumber,
+        level: Level,
+        origin: Option<Card>,
+        cmd: &mut Commands,
+    );
+}
+
+impl SpawnUnit for Unit {
+    fn spawn(
+        &self,
+        pos: ArenaPos,
+        player_num: PlayerNumber,
+        level: Level,
+        origin: Option<Card>,
+        cmd: &mut Commands,
+    ) {
+        cmd.trigger(SpawnUnitEvent(*self, pos, player_num, level, origin));
+    }
+}
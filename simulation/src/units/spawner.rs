@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+use common::{ArenaPos, Level, PlayerNumber, SpawnerStats, Unit};
+
+use crate::{ai::DeployTimer, clock::TICK, SimSet};
+
+use super::{Origin, SpawnUnit};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(FixedUpdate, update_spawners.in_set(SimSet::Units));
+}
+
+/// Постройка, из которой периодически выходят юниты
+#[derive(Component)]
+pub(super) struct Spawner {
+    unit: Unit,
+    cooldown: Timer,
+}
+impl From<&SpawnerStats> for Spawner {
+    fn from(stats: &SpawnerStats) -> Self {
+        Self {
+            unit: stats.unit,
+            cooldown: Timer::from_seconds(stats.interval, TimerMode::Repeating),
+        }
+    }
+}
+
+fn update_spawners(
+    mut spawners: Query<
+        (
+            &mut Spawner,
+            &ArenaPos,
+            &PlayerNumber,
+            &Level,
+            Option<&Origin>,
+        ),
+        Without<DeployTimer>,
+    >,
+    mut cmd: Commands,
+) {
+    for (mut spawner, &ArenaPos(x, y), &player_num, &level, origin) in &mut spawners {
+        if !spawner.cooldown.tick(TICK).just_finished() {
+            continue;
+        }
+
+        // Юнит появляется перед постройкой со стороны противника
+        let front = match player_num {
+            PlayerNumber::One => 1.5,
+            PlayerNumber::Two => -1.5,
+        };
+        // Юниты постройки того же уровня, что и она сама
+        spawner.unit.spawn(
+            ArenaPos(x, y + front),
+            player_num,
+            level,
+            origin.map(|o| o.0),
+            &mut cmd,
+        );
+    }
+}
@@ -0,0 +1,69 @@
+//! Два прогона одних и тех же розыгрышей с одним seed должны совпадать
+//! на каждом тике. Иначе записи матчей и проверка рассинхронизации бесполезны
+
+use std::path::Path;
+
+use common::{load_balance, ArenaPos, Card, Level, PlayerNumber};
+use simulation::{CardPlay, HeadlessMatch, StateHash, TICK};
+
+/// Юниты, постройки и заклинания с обеих сторон. Изба разрушается по времени
+/// и выпускает Русов со случайного угла, так что seed влияет на матч
+fn plays() -> Vec<CardPlay> {
+    use PlayerNumber::*;
+
+    [
+        (1., One, Card::Hut, ArenaPos(-3.5, -6.5)),
+        (1., Two, Card::Giant, ArenaPos(-5.5, 4.5)),
+        (3., One, Card::Musketeer, ArenaPos(-4.5, -9.5)),
+        (4., Two, Card::Priest, ArenaPos(-4.5, 6.5)),
+        (8., One, Card::Fireball, ArenaPos(-5.5, -4.)),
+        (9., Two, Card::Bats, ArenaPos(5.5, 3.5)),
+        (10., One, Card::Bomber, ArenaPos(4.5, -6.5)),
+        (14., Two, Card::Zap, ArenaPos(4.5, -6.)),
+        (16., One, Card::Rus, ArenaPos(5.5, -2.5)),
+        (18., Two, Card::Cannon, ArenaPos(3.5, 6.5)),
+        (22., One, Card::Rage, ArenaPos(5.5, 2.)),
+        (25., Two, Card::Freeze, ArenaPos(5.5, 6.)),
+    ]
+    .into_iter()
+    .map(|(secs, player, card, placement)| CardPlay {
+        tick: (secs / TICK.as_secs_f32()).round() as u64,
+        player,
+        card,
+        level: Level::default(),
+        placement,
+    })
+    .collect()
+}
+
+fn run(seed: u64, secs: f32) -> Vec<StateHash> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets");
+    let (units, cards) = load_balance(&dir).unwrap_or_else(|e| panic!("{e}"));
+    let mut sim = HeadlessMatch::new(units, cards, seed);
+    for play in plays() {
+        sim.play(play);
+    }
+    let ticks = (secs / TICK.as_secs_f32()).round() as u64;
+    (0..ticks)
+        .map(|_| {
+            sim.step();
+            sim.state_hash()
+        })
+        .collect()
+}
+
+#[test]
+fn same_seed_same_match() {
+    let first = run(7, 50.);
+    let second = run(7, 50.);
+    for (a, b) in first.iter().zip(&second) {
+        assert_eq!(a, b, "Прогоны разошлись на тике {}", a.tick);
+    }
+}
+
+#[test]
+fn seed_changes_match() {
+    let first = run(7, 50.);
+    let second = run(8, 50.);
+    assert_ne!(first.last(), second.last());
+}
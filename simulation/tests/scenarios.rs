@@ -0,0 +1,218 @@
+//! Сценарии боя из файлов в tests/scenarios: расстановка юнитов, длительность
+//! и ожидаемый итог. Запускаются в HeadlessMatch без башен, если их нет в расстановке.
+//! Все несовпадения собираются в один отчёт вместе с состоянием юнитов на конце
+
+use std::{fmt::Write, fs, path::Path};
+
+use bevy::utils::HashMap;
+use common::{
+    load_balance, load_ron, ArenaPos, CardsData, Health, Level, PlayerNumber, Unit, UnitState,
+    UnitsData,
+};
+use serde::Deserialize;
+use simulation::{HeadlessMatch, SimEvent, TICK};
+
+#[derive(Deserialize)]
+struct Scenario {
+    description: String,
+    #[serde(default)]
+    seed: u64,
+    // Секунды
+    duration: f32,
+    units: Vec<Placement>,
+    expect: Vec<Expectation>,
+}
+
+#[derive(Deserialize)]
+struct Placement {
+    // Имя, по которому на юнита ссылаются ожидания
+    name: String,
+    unit: Unit,
+    pos: ArenaPos,
+    owner: PlayerNumber,
+    #[serde(default = "first_level")]
+    level: u8,
+}
+
+fn first_level() -> u8 {
+    Level::default().0
+}
+
+#[derive(Deserialize)]
+struct Expectation {
+    unit: String,
+    #[serde(default = "alive")]
+    alive: bool,
+    health: Option<u16>,
+    #[serde(default)]
+    tolerance: u16,
+    state: Option<UnitState>,
+    // Точка и допустимое расстояние до неё
+    near: Option<(ArenaPos, f32)>,
+}
+
+fn alive() -> bool {
+    true
+}
+
+/// Юнит на конце сценария. Погибшие остаются с тиком гибели
+struct Outcome {
+    name: String,
+    unit: Unit,
+    owner: PlayerNumber,
+    state: Result<(ArenaPos, UnitState, Health), u64>,
+}
+
+fn run(scenario: &Scenario, units: &UnitsData, cards: &CardsData) -> Vec<Outcome> {
+    let mut sim = HeadlessMatch::empty(units.clone(), cards.clone(), scenario.seed);
+    let placed: Vec<_> = scenario
+        .units
+        .iter()
+        .map(|p| {
+            let entity = sim.spawn(p.unit, p.pos, p.owner, Level::new(p.level));
+            (entity, p)
+        })
+        .collect();
+
+    let ticks = (scenario.duration / TICK.as_secs_f32()).round() as u64;
+    let mut died = HashMap::new();
+    for _ in 0..ticks {
+        for event in sim.step() {
+            if let SimEvent::UnitDied(entity) = event {
+                died.insert(entity, sim.tick());
+            }
+        }
+    }
+
+    let world = sim.world_mut();
+    let mut query = world.query::<(&ArenaPos, &UnitState, &Health)>();
+    placed
+        .into_iter()
+        .map(|(entity, placement)| Outcome {
+            name: placement.name.clone(),
+            unit: placement.unit,
+            owner: placement.owner,
+            state: match query.get(world, entity) {
+                Ok((pos, state, health)) => Ok((*pos, *state, *health)),
+                Err(_) => Err(died.get(&entity).copied().unwrap_or_default()),
+            },
+        })
+        .collect()
+}
+
+/// Несовпадения с ожиданиями, по строке на каждое
+fn check(scenario: &Scenario, outcomes: &[Outcome]) -> Vec<String> {
+    let mut failures = Vec::new();
+    for expect in &scenario.expect {
+        let Some(outcome) = outcomes.iter().find(|o| o.name == expect.unit) else {
+            failures.push(format!("{}: нет такого юнита в расстановке", expect.unit));
+            continue;
+        };
+        let (pos, state, health) = match (outcome.state, expect.alive) {
+            (Ok(state), true) => state,
+            (Ok(_), false) => {
+                failures.push(format!("{}: жив, ожидалась гибель", expect.unit));
+                continue;
+            }
+            (Err(tick), true) => {
+                failures.push(format!(
+                    "{}: погиб на тике {tick}, ожидалось выживание",
+                    expect.unit
+                ));
+                continue;
+            }
+            (Err(_), false) => continue,
+        };
+        if let Some(expected) = expect.health {
+            if health.0.abs_diff(expected) > expect.tolerance {
+                failures.push(format!(
+                    "{}: здоровье {}, ожидалось {expected} ± {}",
+                    expect.unit, health.0, expect.tolerance
+                ));
+            }
+        }
+        if let Some(expected) = expect.state {
+            if state != expected {
+                failures.push(format!(
+                    "{}: состояние {state:?}, ожидалось {expected:?}",
+                    expect.unit
+                ));
+            }
+        }
+        if let Some((point, radius)) = expect.near {
+            let distance = pos.distance(&point);
+            if distance > radius {
+                failures.push(format!(
+                    "{}: в ({:.1}, {:.1}), в {distance:.1} от ({:.1}, {:.1}), ожидалось не дальше {radius}",
+                    expect.unit, pos.0, pos.1, point.0, point.1
+                ));
+            }
+        }
+    }
+    failures
+}
+
+fn report(
+    file: &str,
+    scenario: &Scenario,
+    failures: &[String],
+    outcomes: &[Outcome],
+) -> String {
+    let mut out = String::new();
+    writeln!(out, "{file}: {}", scenario.description).unwrap();
+    for failure in failures {
+        writeln!(out, "  - {failure}").unwrap();
+    }
+    writeln!(out, "  Юниты через {} с:", scenario.duration).unwrap();
+    for outcome in outcomes {
+        let state = match outcome.state {
+            Ok((pos, state, health)) => format!(
+                "({:.1}, {:.1}) {state:?} {}/{}",
+                pos.0, pos.1, health.0, health.1
+            ),
+            Err(tick) => format!("погиб на тике {tick}"),
+        };
+        writeln!(
+            out,
+            "    {:<10} {:<12} {:<4} {state}",
+            outcome.name,
+            format!("{:?}", outcome.unit),
+            format!("{:?}", outcome.owner),
+        )
+        .unwrap();
+    }
+    out
+}
+
+#[test]
+fn scenarios() {
+    let assets = Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets");
+    let (units, cards) = load_balance(&assets).unwrap_or_else(|e| panic!("{e}"));
+
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/scenarios");
+    let mut files: Vec<_> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "ron"))
+        .collect();
+    files.sort();
+    assert!(!files.is_empty(), "Нет сценариев в {}", dir.display());
+
+    let mut reports = Vec::new();
+    for path in &files {
+        let scenario: Scenario = load_ron(path).unwrap_or_else(|e| panic!("{e}"));
+        let outcomes = run(&scenario, &units, &cards);
+        let failures = check(&scenario, &outcomes);
+        if !failures.is_empty() {
+            let file = path.file_name().unwrap().to_string_lossy();
+            reports.push(report(&file, &scenario, &failures, &outcomes));
+        }
+    }
+    assert!(
+        reports.is_empty(),
+        "Не прошло сценариев: {} из {}\n\n{}",
+        reports.len(),
+        files.len(),
+        reports.join("\n")
+    );
+}